@@ -0,0 +1,63 @@
+// Most codes are only used for sending input through uinput.
+#![cfg_attr(not(feature="uinput"), allow(dead_code))]
+
+// Name prefix of the virtual devices created by the uinput backend, used to recognize injected events.
+pub(crate) const VIRTUAL_DEVICE_NAME_PREFIX:&str = "key_flow virtual";
+
+// Event types and codes from 'linux/input-event-codes.h'.
+pub(crate) const EV_SYN:u16 = 0x00;
+pub(crate) const EV_KEY:u16 = 0x01;
+pub(crate) const EV_REL:u16 = 0x02;
+pub(crate) const EV_ABS:u16 = 0x03;
+pub(crate) const SYN_REPORT:u16 = 0x00;
+pub(crate) const REL_X:u16 = 0x00;
+pub(crate) const REL_Y:u16 = 0x01;
+pub(crate) const REL_HWHEEL:u16 = 0x06;
+pub(crate) const REL_WHEEL:u16 = 0x08;
+pub(crate) const ABS_X:u16 = 0x00;
+pub(crate) const ABS_Y:u16 = 0x01;
+pub(crate) const BTN_LEFT:u16 = 0x110;
+pub(crate) const BTN_RIGHT:u16 = 0x111;
+pub(crate) const BTN_MIDDLE:u16 = 0x112;
+pub(crate) const BTN_SIDE:u16 = 0x113;
+pub(crate) const BTN_EXTRA:u16 = 0x114;
+
+// Pairs of virtual key-codes and evdev key-codes. When multiple virtual keys map to the same evdev key, the first one is used for converting back to a virtual key.
+const KEY_CODE_TABLE:&[(u8, u16)] = &[
+	(0x01, BTN_LEFT), (0x02, BTN_RIGHT), (0x04, BTN_MIDDLE), (0x05, BTN_SIDE), (0x06, BTN_EXTRA),
+	(0x08, 14), (0x09, 15), (0x0D, 28), (0x13, 119), (0x14, 58), (0x1B, 1), (0x20, 57),
+	(0x21, 104), (0x22, 109), (0x23, 107), (0x24, 102), (0x25, 105), (0x26, 103), (0x27, 106), (0x28, 108),
+	(0x29, 0x161), (0x2A, 210), (0x2C, 99), (0x2D, 110), (0x2E, 111), (0x2F, 138),
+	(0x30, 11), (0x31, 2), (0x32, 3), (0x33, 4), (0x34, 5), (0x35, 6), (0x36, 7), (0x37, 8), (0x38, 9), (0x39, 10),
+	(0x41, 30), (0x42, 48), (0x43, 46), (0x44, 32), (0x45, 18), (0x46, 33), (0x47, 34), (0x48, 35), (0x49, 23),
+	(0x4A, 36), (0x4B, 37), (0x4C, 38), (0x4D, 50), (0x4E, 49), (0x4F, 24), (0x50, 25), (0x51, 16), (0x52, 19),
+	(0x53, 31), (0x54, 20), (0x55, 22), (0x56, 47), (0x57, 17), (0x58, 45), (0x59, 21), (0x5A, 44),
+	(0x5B, 125), (0x5C, 126), (0x5D, 127), (0x5F, 142),
+	(0x60, 82), (0x61, 79), (0x62, 80), (0x63, 81), (0x64, 75), (0x65, 76), (0x66, 77), (0x67, 71), (0x68, 72), (0x69, 73),
+	(0x6A, 55), (0x6B, 78), (0x6C, 121), (0x6D, 74), (0x6E, 83), (0x6F, 98),
+	(0x70, 59), (0x71, 60), (0x72, 61), (0x73, 62), (0x74, 63), (0x75, 64), (0x76, 65), (0x77, 66), (0x78, 67), (0x79, 68), (0x7A, 87), (0x7B, 88),
+	(0x7C, 183), (0x7D, 184), (0x7E, 185), (0x7F, 186), (0x80, 187), (0x81, 188), (0x82, 189), (0x83, 190), (0x84, 191), (0x85, 192), (0x86, 193), (0x87, 194),
+	(0x90, 69), (0x91, 70),
+	(0xA0, 42), (0xA1, 54), (0xA2, 29), (0xA3, 97), (0xA4, 56), (0xA5, 100),
+	(0xA6, 158), (0xA7, 159), (0xA8, 173), (0xA9, 128), (0xAA, 217), (0xAB, 156), (0xAC, 172),
+	(0xAD, 113), (0xAE, 114), (0xAF, 115), (0xB0, 163), (0xB1, 165), (0xB2, 166), (0xB3, 164),
+	(0xBA, 39), (0xBB, 13), (0xBC, 51), (0xBD, 12), (0xBE, 52), (0xBF, 53), (0xC0, 41), (0xDB, 26), (0xDC, 43), (0xDD, 27), (0xDE, 40),
+	(0x10, 42), (0x11, 29), (0x12, 56)
+];
+
+
+
+/// Get the evdev key-code for a virtual key-code.
+pub(crate) fn key_code_to_evdev(key_code:u8) -> Option<u16> {
+	KEY_CODE_TABLE.iter().find(|(virtual_code, _)| *virtual_code == key_code).map(|(_, evdev_code)| *evdev_code)
+}
+
+/// Get the virtual key-code for an evdev key-code.
+pub(crate) fn evdev_to_key_code(evdev_code:u16) -> Option<u8> {
+	KEY_CODE_TABLE.iter().find(|(_, code)| *code == evdev_code).map(|(virtual_code, _)| *virtual_code)
+}
+
+/// Get all evdev key-codes that have a virtual key-code.
+pub(crate) fn evdev_key_codes() -> impl Iterator<Item = u16> {
+	KEY_CODE_TABLE.iter().map(|(_, evdev_code)| *evdev_code)
+}
\ No newline at end of file
@@ -0,0 +1,97 @@
+use std::{ panic::{ self, AssertUnwindSafe }, sync::{ Arc, RwLock, RwLockWriteGuard, mpsc::{ self, Receiver, Sender } }, thread };
+
+
+
+static HANDLER_EXECUTOR:RwLock<Option<Arc<dyn HandlerExecutor>>> = RwLock::new(None);
+const DEFAULT_WORKER_COUNT:usize = 4;
+pub(crate) type HotkeyHandler = Arc<dyn Fn() + Send + Sync>;
+type HandlerJob = Box<dyn FnOnce() + Send>;
+
+
+
+pub trait HandlerExecutor:Send + Sync {
+
+	/// Execute a handler of a hotkey. Handlers of the same hotkey should execute in the order they are received.
+	fn execute(&self, hotkey_id:u64, handler:Box<dyn FnOnce() + Send>);
+}
+
+
+
+/// Executes handlers immediately on the thread that triggers them, which is usually the thread of the hook.
+pub struct InlineExecutor;
+impl HandlerExecutor for InlineExecutor {
+	fn execute(&self, _hotkey_id:u64, handler:Box<dyn FnOnce() + Send>) {
+		handler();
+	}
+}
+
+
+
+/// Executes handlers on a fixed amount of worker threads. All handlers of a hotkey execute on the same worker, so they execute in order.
+pub struct WorkerPool {
+	workers:Vec<Sender<HandlerJob>>
+}
+impl WorkerPool {
+
+	/// Create a new pool and start its workers.
+	pub fn new(worker_count:usize) -> WorkerPool {
+		WorkerPool {
+			workers: (0..worker_count.max(1)).map(|_| {
+				let (sender, receiver):(Sender<HandlerJob>, Receiver<HandlerJob>) = mpsc::channel();
+				thread::spawn(move || {
+					for handler in receiver {
+						let _ = panic::catch_unwind(AssertUnwindSafe(handler)); // A panicking handler should not stop the worker.
+					}
+				});
+				sender
+			}).collect()
+		}
+	}
+}
+impl HandlerExecutor for WorkerPool {
+	fn execute(&self, hotkey_id:u64, handler:Box<dyn FnOnce() + Send>) {
+		let _ = self.workers[hotkey_id as usize % self.workers.len()].send(handler);
+	}
+}
+
+
+
+/// Decides how the handlers of a hotkey are executed.
+#[derive(Clone, Copy)]
+pub(crate) struct HandlerDispatcher {
+	hotkey_id:u64,
+	inline:bool
+}
+impl HandlerDispatcher {
+
+	/// Create a dispatcher for the handlers of a hotkey. Inline handlers execute immediately, others are sent to the handler executor.
+	pub(crate) fn new(hotkey_id:u64, inline:bool) -> HandlerDispatcher {
+		HandlerDispatcher { hotkey_id, inline }
+	}
+
+	/// Execute a handler. Inline handlers that panic do not unwind into the hook.
+	pub(crate) fn dispatch(&self, handler:&HotkeyHandler) {
+		if self.inline {
+			let _ = panic::catch_unwind(AssertUnwindSafe(|| handler()));
+		} else {
+			let handler:HotkeyHandler = handler.clone();
+			handler_executor().execute(self.hotkey_id, Box::new(move || handler()));
+		}
+	}
+}
+
+
+
+/// Set the executor the handlers of hotkeys are executed by, unless they are inline.
+pub fn set_handler_executor<T>(executor:T) where T:HandlerExecutor + 'static {
+	*HANDLER_EXECUTOR.write().unwrap() = Some(Arc::new(executor));
+}
+
+/// Get the executor the handlers of hotkeys are executed by. Defaults to a worker pool.
+pub fn handler_executor() -> Arc<dyn HandlerExecutor> {
+	if let Some(executor) = HANDLER_EXECUTOR.read().unwrap().as_ref() {
+		return executor.clone();
+	}
+	let mut executor:RwLockWriteGuard<'_, Option<Arc<dyn HandlerExecutor>>> = HANDLER_EXECUTOR.write().unwrap();
+	executor.get_or_insert_with(|| Arc::new(WorkerPool::new(DEFAULT_WORKER_COUNT))).clone()
+}
\ No newline at end of file
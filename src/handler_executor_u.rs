@@ -0,0 +1,51 @@
+#[cfg(test)]
+mod tests {
+	use crate::{ HandlerExecutor, Hotkey, WorkerPool, hokey_u::tests::HOTKEY_LOCK, key_hook::simulate, keys };
+	use std::{ sync::{ Arc, Mutex, MutexGuard, atomic::{ AtomicUsize, Ordering }, mpsc::{ self, Receiver, Sender } }, thread, time::{ Duration, Instant } };
+
+
+
+	#[test]
+	fn test_worker_pool_order() {
+		let pool:WorkerPool = WorkerPool::new(3);
+		let executed:Arc<Mutex<Vec<usize>>> = Arc::new(Mutex::new(Vec::new()));
+		for index in 0..100 {
+			let executed:Arc<Mutex<Vec<usize>>> = executed.clone();
+			pool.execute(7, Box::new(move || executed.lock().unwrap().push(index)));
+		}
+		let (sender, receiver):(Sender<()>, Receiver<()>) = mpsc::channel();
+		pool.execute(7, Box::new(move || sender.send(()).unwrap()));
+		receiver.recv_timeout(Duration::from_secs(2)).unwrap();
+		assert_eq!(*executed.lock().unwrap(), (0..100).collect::<Vec<usize>>());
+	}
+
+	#[test]
+	fn test_worker_pool_survives_panic() {
+		let pool:WorkerPool = WorkerPool::new(1);
+		pool.execute(0, Box::new(|| panic!("Handler panic")));
+		let (sender, receiver):(Sender<()>, Receiver<()>) = mpsc::channel();
+		pool.execute(0, Box::new(move || sender.send(()).unwrap()));
+		assert!(receiver.recv_timeout(Duration::from_secs(2)).is_ok());
+	}
+
+	#[test]
+	fn test_slow_handler_does_not_stall_hook() {
+		let _lock:MutexGuard<'_, ()> = HOTKEY_LOCK.lock().unwrap();
+		let press_count:Arc<AtomicUsize> = Arc::new(AtomicUsize::new(0));
+		let handler_count:Arc<AtomicUsize> = press_count.clone();
+		Hotkey::new(&[keys::PAUSE]).on_press(move || {
+			thread::sleep(Duration::from_millis(200));
+			handler_count.fetch_add(1, Ordering::SeqCst);
+		}).register();
+
+		let start:Instant = Instant::now();
+		simulate(keys::PAUSE, true);
+		simulate(keys::PAUSE, false);
+		assert!(start.elapsed() < Duration::from_millis(100));
+		assert_eq!(press_count.load(Ordering::SeqCst), 0);
+		while press_count.load(Ordering::SeqCst) == 0 && start.elapsed() < Duration::from_secs(2) {
+			thread::sleep(Duration::from_millis(5));
+		}
+		assert_eq!(press_count.load(Ordering::SeqCst), 1);
+	}
+}
\ No newline at end of file
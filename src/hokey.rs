@@ -1,34 +1,58 @@
 use circular_buffer::CircularBuffer;
-use crate::{ Key, KeyPattern };
-
-
-
-// As the mutations of hotkeys are done using 'mutation_lock', the list of hotkeys is only mutated by the key_hook thread. This means no Mutex is required for this list.
-pub(crate) static mut REGISTERED_HOTKEYS:Vec<Hotkey> = Vec::new();
+use crate::{ HotkeyContext, HotkeyEvent, Key, KeyPattern, MatchMode, Propagation, WindowMatcher, handler_executor::{ HandlerDispatcher, HotkeyHandler }, hotkey_gestures::HotkeyGestures, hotkey_registry::REGISTERED_HOTKEYS, hotkeys::HotkeyInfo, hotkey_sequence::{ HotkeySequence, SequenceState }, key_hook::PHYSICAL_KEY_STATES, layers };
+use std::{ ops::{ Deref, DerefMut }, panic::{ self, AssertUnwindSafe }, sync::{ Arc, atomic::{ AtomicU64, Ordering } }, time::{ Duration, SystemTime } };
 
 
 
 #[derive(Clone, Copy)]
-enum ModificationRequest { Enable, Disable, Toggle, None }
+pub(crate) enum ModificationRequest { Enable, Disable, Toggle, Unregister, None }
 impl Default for ModificationRequest {
 	fn default() -> Self {
 		ModificationRequest::None
 	}
 }
 const MODIFICATIONS_QUEUE_SIZE:usize = 64;
+type HotkeyPredicate = Box<dyn Fn(&HotkeyContext) -> bool + Send + Sync>;
+type EventHandler = Box<dyn Fn(&HotkeyEvent) -> Propagation + Send + Sync>;
+
+
+
+pub(crate) enum Handler {
+	Plain(HotkeyHandler), // Blocks depending on the blocking flag of the hotkey.
+	Event(EventHandler) // Decides whether to block, so always executes inline.
+}
+
+/// A request to change the keys or handlers of a registered hotkey. Unlike modification requests, these are applied by the registry on the next update.
+pub(crate) enum Rebind {
+	Keys(KeyPattern),
+	OnPress(Handler),
+	OnRepeat(Handler),
+	OnRelease(Handler)
+}
 
 
 
 pub struct Hotkey {
 	id:u64,
 	key_pattern:KeyPattern,
-	on_press:Option<Box<dyn Fn() + Send + Sync>>,
-	on_repeat:Option<Box<dyn Fn() + Send + Sync>>,
-	on_release:Option<Box<dyn Fn() + Send + Sync>>,
+	match_mode:MatchMode,
+	trigger:Option<KeyPattern>,
+	trigger_held:bool,
+	layer:Option<String>,
+	predicate:Option<HotkeyPredicate>,
+	windows:Vec<WindowMatcher>,
+	on_press:Option<Handler>,
+	on_repeat:Option<Handler>,
+	on_release:Option<Handler>,
+	gestures:HotkeyGestures,
 	blocking:bool,
+	inline:bool,
+	press_blocked:bool,
+	repeat_count:u32,
 	state:bool,
 	enabled:bool,
 	registered:bool,
+	sequence:Option<HotkeySequence>,
 
 	modifications_queue:CircularBuffer<ModificationRequest, MODIFICATIONS_QUEUE_SIZE> // Only one thread reads and writes this, so no async handling required
 }
@@ -38,37 +62,100 @@ impl Hotkey {
 
 	/// Create a new hotkey.
 	pub fn new(keys:&[Key]) -> Hotkey {
-		static mut ID_GENERATOR:u64 = 0;
+		static ID_GENERATOR:AtomicU64 = AtomicU64::new(0);
 		Hotkey {
-			id: unsafe { ID_GENERATOR += 1; ID_GENERATOR },
-			key_pattern: keys.iter().map(|key| key.pattern()).reduce(|a, b| a ^ b).unwrap_or_default(),
+			id: ID_GENERATOR.fetch_add(1, Ordering::SeqCst) + 1,
+			key_pattern: keys_pattern(keys),
+			match_mode: MatchMode::Superset,
+			trigger: None,
+			trigger_held: false,
+			layer: None,
+			predicate: None,
+			windows: Vec::new(),
 			on_press: None,
-			on_repeat: Some(Box::new(|| {})), // When blocking and on-press are enabled, makes sure on-repeat blocks too.
+			on_repeat: Some(Handler::Plain(Arc::new(|| {}))), // When the press is blocked, makes sure on-repeat blocks too.
 			on_release: None,
+			gestures: HotkeyGestures::default(),
 			blocking: false,
+			inline: false,
+			press_blocked: false,
+			repeat_count: 0,
 			state: false,
 			enabled: true,
 			registered: false,
+			sequence: None,
 
 			modifications_queue: CircularBuffer::new()
 		}
 	}
 
+	/// Create a new hotkey that triggers when multiple combinations of keys are pressed in order, like 'Ctrl+K, Ctrl+C'. The handlers apply to the final combination.
+	pub fn sequence(steps:&[&[Key]]) -> Hotkey {
+		let step_patterns:Vec<KeyPattern> = steps.iter().map(|keys| keys_pattern(keys)).collect();
+		let mut hotkey:Hotkey = Hotkey::new(steps.last().copied().unwrap_or_default());
+		if step_patterns.len() > 1 {
+			hotkey.sequence = Some(HotkeySequence::new(step_patterns[..step_patterns.len() - 1].to_vec()));
+		}
+		hotkey
+	}
+
 	/// Return self with a handler that triggers when all keys are pressed.
 	pub fn on_press<T>(mut self, handler:T) -> Self where T:Fn() + 'static + Send + Sync {
-		self.on_press = Some(Box::new(handler));
+		self.on_press = Some(Handler::Plain(Arc::new(handler)));
 		self
 	}
 
 	/// Return self with a handler that triggers when all keys are pressed and any of the keys is repeating.
 	pub fn on_repeat<T>(mut self, handler:T) -> Self where T:Fn() + 'static + Send + Sync {
-		self.on_repeat = Some(Box::new(handler));
+		self.on_repeat = Some(Handler::Plain(Arc::new(handler)));
 		self
 	}
 
 	/// Return self with a handler that triggers when any of the keys are released after they were all held.
 	pub fn on_release<T>(mut self, handler:T) -> Self where T:Fn() + 'static + Send + Sync {
-		self.on_release = Some(Box::new(handler));
+		self.on_release = Some(Handler::Plain(Arc::new(handler)));
+		self
+	}
+
+	/// Return self with a handler that triggers when all keys are pressed and decides whether to block the key event. The handler executes inline, on the thread of the hook.
+	pub fn on_press_event<T>(mut self, handler:T) -> Self where T:Fn(&HotkeyEvent) -> Propagation + 'static + Send + Sync {
+		self.on_press = Some(Handler::Event(Box::new(handler)));
+		self
+	}
+
+	/// Return self with a handler that triggers when all keys are pressed and any of the keys is repeating, and decides whether to block the key event. The handler executes inline, on the thread of the hook.
+	pub fn on_repeat_event<T>(mut self, handler:T) -> Self where T:Fn(&HotkeyEvent) -> Propagation + 'static + Send + Sync {
+		self.on_repeat = Some(Handler::Event(Box::new(handler)));
+		self
+	}
+
+	/// Return self with a handler that triggers when any of the keys are released after they were all held, and decides whether to block the key event. The handler executes inline, on the thread of the hook.
+	pub fn on_release_event<T>(mut self, handler:T) -> Self where T:Fn(&HotkeyEvent) -> Propagation + 'static + Send + Sync {
+		self.on_release = Some(Handler::Event(Box::new(handler)));
+		self
+	}
+
+	/// Return self with a handler that triggers when the keys are released within the given duration after being pressed.
+	pub fn on_tap<T>(mut self, max_duration:Duration, handler:T) -> Self where T:Fn() + 'static + Send + Sync {
+		self.gestures.on_tap = Some((max_duration, Arc::new(handler)));
+		self
+	}
+
+	/// Return self with a handler that triggers once the keys have been held for the given duration, without waiting for them to be released.
+	pub fn on_hold<T>(mut self, duration:Duration, handler:T) -> Self where T:Fn() + 'static + Send + Sync {
+		self.gestures.on_hold = Some((duration, Arc::new(handler)));
+		self
+	}
+
+	/// Return self with a handler that triggers when the keys are pressed again within the given window after a tap. The second tap does not trigger the tap handler. Without a tap handler, presses of at most the window count as taps.
+	pub fn on_double_tap<T>(mut self, window:Duration, handler:T) -> Self where T:Fn() + 'static + Send + Sync {
+		self.gestures.on_double_tap = Some((window, Arc::new(handler)));
+		self
+	}
+
+	/// Return self with a handler that triggers when the keys are released after being held for at least the given duration. Does not trigger if the hold handler already triggered.
+	pub fn on_long_press<T>(mut self, min_duration:Duration, handler:T) -> Self where T:Fn() + 'static + Send + Sync {
+		self.gestures.on_long_press = Some((min_duration, Arc::new(handler)));
 		self
 	}
 
@@ -78,6 +165,52 @@ impl Hotkey {
 		self
 	}
 
+	/// Return self with inline handlers. Inline handlers execute on the thread of the hook, delaying all input until they return, so they should return quickly.
+	/// By default, handlers are sent to the handler executor, which executes them on worker threads.
+	pub fn inline(mut self) -> Self {
+		self.inline = true;
+		self
+	}
+
+	/// Return self with a trigger key. The hotkey only triggers when the trigger key is pressed while all other keys are already held. The trigger key is added to the keys of the hotkey.
+	pub fn trigger(mut self, key:Key) -> Self {
+		self.key_pattern |= key.pattern();
+		self.trigger = Some(key.pattern());
+		self
+	}
+
+	/// Return self assigned to a layer. The hotkey can only trigger while the layer is active, and takes precedence over hotkeys with the same keys on lower layers while its predicate and window scope allow it to be pressed.
+	pub fn layer(mut self, layer:&str) -> Self {
+		self.layer = Some(layer.to_string());
+		self
+	}
+
+	/// Return self with a predicate. The hotkey can only be pressed while the predicate returns true for the current context. Releasing the hotkey does not depend on the predicate.
+	pub fn when<T>(mut self, predicate:T) -> Self where T:Fn(&HotkeyContext) -> bool + 'static + Send + Sync {
+		self.predicate = Some(Box::new(predicate));
+		self
+	}
+
+	/// Return self scoped to windows. The hotkey can only be pressed while the active window matches any of the matchers it is scoped to.
+	pub fn window(mut self, matcher:WindowMatcher) -> Self {
+		self.windows.push(matcher);
+		self
+	}
+
+	/// Return self with a different way of matching held keys. By default, the hotkey triggers regardless of any other held keys.
+	pub fn match_mode(mut self, match_mode:MatchMode) -> Self {
+		self.match_mode = match_mode;
+		self
+	}
+
+	/// Return self with a maximum time between two steps of a sequence. When exceeded, the sequence starts over. Has no effect on hotkeys that are not sequences.
+	pub fn step_timeout(mut self, timeout:Duration) -> Self {
+		if let Some(sequence) = &mut self.sequence {
+			sequence.set_step_timeout(timeout);
+		}
+		self
+	}
+
 	/// Return self, but disabled.
 	pub fn disabled(mut self) -> Self {
 		self.enabled = false;
@@ -89,26 +222,56 @@ impl Hotkey {
 	/* REGISTERED STATIC METHODS */
 
 	/// Register the hotkey to the static list. 
-	#[allow(static_mut_refs)]
 	pub fn register(mut self) -> HotkeyHandle {
-		// As this consumes self, mutation lock is not required.
 
 		// Set self as registered and create a handle.
-		self.registered = true;
-		let handle:HotkeyHandle = HotkeyHandle(self.id);
+		let handle:HotkeyHandle = self.mark_registered();
 
 		// Push the hotkey to the registered hotkeys list.
-		unsafe {
-			if let Some(existing_index) = REGISTERED_HOTKEYS.iter().position(|existing_hotkey| existing_hotkey == &self) {
-				REGISTERED_HOTKEYS[existing_index] = self;
-			} else {
-				REGISTERED_HOTKEYS.push(self);
-			}
-		}
+		REGISTERED_HOTKEYS.register(vec![self]);
 		
 		handle
 	}
 
+	/// Set self as registered and create a handle. The hotkey still has to be added to the registry.
+	pub(crate) fn mark_registered(&mut self) -> HotkeyHandle {
+		self.registered = true;
+		HotkeyHandle(self.id)
+	}
+
+
+
+	/// Register the hotkey and wait until it is pressed. The on-press handler still runs. After being pressed, or when the future is dropped before that, the hotkey is unregistered.
+	#[cfg(feature="async")]
+	pub async fn wait_pressed(mut self) {
+		use futures::channel::oneshot::{ self, Receiver, Sender };
+		use std::sync::Mutex;
+
+		let (sender, receiver):(Sender<()>, Receiver<()>) = oneshot::channel();
+		let sender:Mutex<Option<Sender<()>>> = Mutex::new(Some(sender));
+		let id:u64 = self.id;
+		let notify_pressed = move || {
+			if let Some(sender) = sender.lock().unwrap().take() {
+				let _ = sender.send(());
+				REGISTERED_HOTKEYS.unregister(&[id]);
+			}
+		};
+		self.on_press = Some(match self.on_press.take() {
+			Some(Handler::Event(on_press)) => Handler::Event(Box::new(move |event| {
+				let propagation:Propagation = on_press(event);
+				notify_pressed();
+				propagation
+			})),
+			Some(Handler::Plain(on_press)) => Handler::Plain(Arc::new(move || {
+				on_press();
+				notify_pressed();
+			})),
+			None => Handler::Plain(Arc::new(notify_pressed))
+		});
+		let _guard:HotkeyGuard = self.register().unregister_on_drop(); // Cancelling the future unregisters the hotkey too.
+		let _ = receiver.await;
+	}
+
 
 
 	/* PROPERTY GETTER METHODS */
@@ -118,54 +281,206 @@ impl Hotkey {
 		self.enabled
 	}
 
+	/// The unique id of the hotkey.
+	pub(crate) fn id(&self) -> u64 {
+		self.id
+	}
+
+	/// Whether or not the keys of the hotkey are pressed.
+	pub(crate) fn pressed(&self) -> bool {
+		self.state
+	}
+
+	/// The keys of the hotkey. For sequences, the keys of the final step.
+	pub(crate) fn key_pattern(&self) -> KeyPattern {
+		self.key_pattern
+	}
+
+	/// The layer the hotkey is assigned to.
+	pub(crate) fn assigned_layer(&self) -> Option<&str> {
+		self.layer.as_deref()
+	}
+
+	/// Create a snapshot of the keys, flags and state of the hotkey.
+	pub(crate) fn info(&self) -> HotkeyInfo {
+		HotkeyInfo {
+			handle: HotkeyHandle(self.id),
+			keys: self.key_pattern,
+			steps: self.sequence.as_ref().map(|sequence| sequence.steps().to_vec()).unwrap_or_default(),
+			trigger: self.trigger,
+			layer: self.layer.clone(),
+			match_mode: self.match_mode,
+			blocking: self.blocking,
+			inline: self.inline,
+			conditional: self.predicate.is_some() || !self.windows.is_empty(),
+			enabled: self.enabled,
+			pressed: self.state
+		}
+	}
+
 
 
 	/* USAGE METHODS */
 
 	/// Create a request to enable the hotkey. Will be applied on the next update.
 	pub fn enable(&mut self) {
-		self.modifications_queue.push(ModificationRequest::Enable);
+		self.request_modification(ModificationRequest::Enable);
 	}
 
 	/// Create a request to disable the hotkey. Will be applied on the next update.
 	pub fn disable(&mut self) {
-		self.modifications_queue.push(ModificationRequest::Disable);
+		self.request_modification(ModificationRequest::Disable);
 	}
 
 	/// Create a request to toggle the hotkey. Will be applied on the next update.
 	pub fn toggle(&mut self) {
-		self.modifications_queue.push(ModificationRequest::Toggle);
+		self.request_modification(ModificationRequest::Toggle);
 	}
 
-	/// Update the current state. Returns true if hotkey blocks.
-	pub(crate) fn update_state(&mut self, state_change_pattern:KeyPattern, active_pattern:&KeyPattern) -> bool {
+	/// Create a request to modify the hotkey. Will be applied on the next update.
+	pub(crate) fn request_modification(&mut self, modification:ModificationRequest) {
+		self.modifications_queue.push(modification);
+	}
 
-		// Handle requested modifications.
+	/// Apply all requested modifications.
+	pub(crate) fn apply_modifications(&mut self) {
 		for modification in self.modifications_queue.take_all() {
 			match modification {
 				ModificationRequest::Enable => self.enabled = true,
 				ModificationRequest::Disable => self.enabled = false,
 				ModificationRequest::Toggle => self.enabled = !self.enabled,
-				ModificationRequest::None => {}
+				ModificationRequest::Unregister | ModificationRequest::None => {} // Unregistering is handled by the registry.
 			}
 		}
+	}
+
+	/// Apply a request to change the keys or handlers. The trigger key stays part of the keys. Changing the keys of a pressed hotkey releases it first.
+	pub(crate) fn rebind(&mut self, rebind:Rebind) {
+		match rebind {
+			Rebind::Keys(key_pattern) => {
+				let key_pattern:KeyPattern = key_pattern | self.trigger.unwrap_or_default();
+				if key_pattern != self.key_pattern {
+					self.release();
+					self.key_pattern = key_pattern;
+				}
+			},
+			Rebind::OnPress(handler) => self.on_press = Some(handler),
+			Rebind::OnRepeat(handler) => self.on_repeat = Some(handler),
+			Rebind::OnRelease(handler) => self.on_release = Some(handler)
+		}
+	}
+
+	/// Update the current state. Unavailable hotkeys, for example because their layer is not active, cannot be pressed, but can still be released. Returns true if hotkey blocks.
+	pub(crate) fn update_state(&mut self, state_change_pattern:KeyPattern, active_pattern:&KeyPattern, available:bool, context:&HotkeyContext) -> bool {
 
-		// If the hotkey is disabled or the changed key does not affect this hotkey, return now.
-		if !self.enabled || state_change_pattern & self.key_pattern == KeyPattern::ZERO {
+		// Handle requested modifications.
+		self.apply_modifications();
+
+		// If the hotkey is disabled or cannot be pressed, return now.
+		if !self.enabled || !self.state && !available {
+			return false;
+		}
+
+		// Sequences only reach their final step after all preceding steps were pressed in order.
+		if let Some(sequence) = &mut self.sequence && let SequenceState::Progressing { consumed } = sequence.update(state_change_pattern, active_pattern, self.key_pattern, self.state) {
+			return consumed && self.blocking;
+		}
+
+		// If the changed key does not affect this hotkey, return now.
+		if state_change_pattern & self.key_pattern == KeyPattern::ZERO {
 			return false;
 		}
 
 		// Update state change.
-		let new_state:bool = self.key_pattern & *active_pattern == self.key_pattern;
-		let mut executed_any:bool = false;
-		if let Some(handler) = if new_state && !self.state { &self.on_press } else if new_state && self.state { &self.on_repeat } else if !new_state && self.state { &self.on_release } else { &None } {
-			handler();
-			executed_any = true;
+		let mut new_state:bool = self.match_mode.matches(self.key_pattern, active_pattern);
+		if let Some(trigger) = self.trigger && state_change_pattern & trigger != KeyPattern::ZERO {
+			if !self.state && new_state && self.trigger_held {
+				new_state = false; // The trigger key is repeating, but was pressed before the other keys.
+			}
+			self.trigger_held = *active_pattern & trigger != KeyPattern::ZERO;
+		} else if self.trigger.is_some() && !self.state {
+			new_state = false; // Another key completed the hotkey, but only the trigger key can.
+		}
+		if new_state && !self.state && !self.allowed_in(context) {
+			new_state = false;
+		}
+		let (pressed, repeated, released):(bool, bool, bool) = (new_state && !self.state, new_state && self.state, !new_state && self.state);
+		if pressed {
+			self.repeat_count = 0;
+			self.press_blocked = false;
+		} else if repeated {
+			self.repeat_count += 1;
+		}
+
+		// Execute handlers. Handlers without a propagation block if the hotkey is blocking or its press was blocked.
+		let dispatcher:HandlerDispatcher = HandlerDispatcher::new(self.id, self.inline);
+		let mut blocking:bool = false;
+		if let Some(handler) = if pressed { &self.on_press } else if repeated { &self.on_repeat } else if released { &self.on_release } else { &None } {
+			blocking = match handler {
+				Handler::Plain(handler) => {
+					dispatcher.dispatch(handler);
+					self.blocking || self.press_blocked
+				},
+				Handler::Event(handler) => handle_event(handler, &self.event(state_change_pattern, active_pattern, context)) == Propagation::Block
+			};
+		}
+		if pressed {
+			self.press_blocked = blocking;
+		}
+		if pressed && self.gestures.press(self.id, &dispatcher) || released && self.gestures.release(&dispatcher) {
+			blocking |= self.blocking;
+		}
+		if released && let Some(sequence) = &mut self.sequence {
+			sequence.reset();
 		}
 		self.state = new_state;
 
 		// Return blocking state.
-		executed_any && self.blocking
+		blocking
+	}
+
+	/// Release the hotkey if it is pressed, executing its release handler while its keys may still be held. Also resets the progress of the trigger key and the sequence, as used when the hotkey is removed or its keys change.
+	pub(crate) fn release(&mut self) {
+		if self.state {
+			match &self.on_release {
+				Some(Handler::Plain(handler)) => HandlerDispatcher::new(self.id, self.inline).dispatch(handler),
+				Some(Handler::Event(handler)) => {
+					let held_keys:KeyPattern = PHYSICAL_KEY_STATES.snapshot();
+					handle_event(handler, &self.event(KeyPattern::ZERO, &held_keys, &HotkeyContext::new(SystemTime::now(), held_keys, layers::active_layers())));
+				},
+				None => {}
+			}
+			self.state = false;
+		}
+		self.trigger_held = false;
+		if let Some(sequence) = &mut self.sequence {
+			sequence.reset();
+		}
+	}
+
+	/// Create the event the handlers of the hotkey receive.
+	fn event(&self, state_change_pattern:KeyPattern, active_pattern:&KeyPattern, context:&HotkeyContext) -> HotkeyEvent {
+		HotkeyEvent {
+			key: state_change_pattern.keys().first().copied().unwrap_or(Key::new(0)),
+			timestamp: context.timestamp(),
+			repeat_count: self.repeat_count,
+			held_keys: *active_pattern,
+			handle: HotkeyHandle(self.id)
+		}
+	}
+
+	/// Check if the predicate and the window scope of the hotkey allow it to be pressed in a context.
+	pub(crate) fn allowed_in(&self, context:&HotkeyContext) -> bool {
+		panic::catch_unwind(AssertUnwindSafe(|| {
+			self.predicate.as_ref().is_none_or(|predicate| predicate(context)) && (self.windows.is_empty() || context.active_window().is_some_and(|window| self.windows.iter().any(|matcher| matcher.matches(window))))
+		})).unwrap_or(false) // A panicking predicate or window source does not unwind into the hook.
+	}
+
+	/// Handle a timer of the hotkey expiring.
+	pub(crate) fn timer_expired(&mut self, generation:u64) {
+		if self.enabled && self.state {
+			self.gestures.timer_expired(generation, &HandlerDispatcher::new(self.id, self.inline));
+		}
 	}
 }
 impl PartialEq for Hotkey {
@@ -183,29 +498,111 @@ impl Drop for Hotkey {
 
 
 
+#[derive(Clone, Debug, PartialEq, Eq)]
 pub struct HotkeyHandle(u64);
 impl HotkeyHandle {
 
-	/// Execute something on the hotkey the handle is assigned to.
-	#[allow(static_mut_refs)]
-	fn execute<T>(&self, execution:T) where T:Fn(&mut Hotkey) {
-		if let Some(hotkey) = unsafe { REGISTERED_HOTKEYS.iter_mut().find(|hotkey| hotkey.id == self.0) } {
-			execution(hotkey);
-		}
+	/// The unique id of the hotkey the handle is assigned to.
+	pub(crate) fn id(&self) -> u64 {
+		self.0
+	}
+
+	/// Create a request to modify the hotkey the handle is assigned to. Will be applied on the next update.
+	fn request_modification(&self, modification:ModificationRequest) {
+		REGISTERED_HOTKEYS.request_modification(&[self.0], modification);
 	}
 
 	/// Enable the hotkey.
 	pub fn enable(&mut self) {
-		self.execute(|hotkey| hotkey.enable());
+		self.request_modification(ModificationRequest::Enable);
 	}
 
 	/// Disable the hotkey.
 	pub fn disable(&mut self) {
-		self.execute(|hotkey| hotkey.disable());
+		self.request_modification(ModificationRequest::Disable);
 	}
 
 	/// Toggle the hotkey.
 	pub fn toggle(&mut self) {
-		self.execute(|hotkey| hotkey.toggle());
+		self.request_modification(ModificationRequest::Toggle);
+	}
+
+	/// Change the keys of the hotkey. For sequences, changes the keys of the final step. A pressed hotkey is released first. Will be applied on the next update.
+	pub fn set_keys(&mut self, keys:&[Key]) {
+		REGISTERED_HOTKEYS.request_rebind(self.0, Rebind::Keys(keys_pattern(keys)));
+	}
+
+	/// Change the handler that triggers when all keys are pressed. Will be applied on the next update.
+	pub fn set_on_press<T>(&mut self, handler:T) where T:Fn() + 'static + Send + Sync {
+		REGISTERED_HOTKEYS.request_rebind(self.0, Rebind::OnPress(Handler::Plain(Arc::new(handler))));
+	}
+
+	/// Change the handler that triggers when all keys are pressed and any of the keys is repeating. Will be applied on the next update.
+	pub fn set_on_repeat<T>(&mut self, handler:T) where T:Fn() + 'static + Send + Sync {
+		REGISTERED_HOTKEYS.request_rebind(self.0, Rebind::OnRepeat(Handler::Plain(Arc::new(handler))));
+	}
+
+	/// Change the handler that triggers when any of the keys are released after they were all held. Will be applied on the next update.
+	pub fn set_on_release<T>(&mut self, handler:T) where T:Fn() + 'static + Send + Sync {
+		REGISTERED_HOTKEYS.request_rebind(self.0, Rebind::OnRelease(Handler::Plain(Arc::new(handler))));
+	}
+
+	/// Change the press handler to one that decides whether to block the key event. Will be applied on the next update.
+	pub fn set_on_press_event<T>(&mut self, handler:T) where T:Fn(&HotkeyEvent) -> Propagation + 'static + Send + Sync {
+		REGISTERED_HOTKEYS.request_rebind(self.0, Rebind::OnPress(Handler::Event(Box::new(handler))));
+	}
+
+	/// Change the repeat handler to one that decides whether to block the key event. Will be applied on the next update.
+	pub fn set_on_repeat_event<T>(&mut self, handler:T) where T:Fn(&HotkeyEvent) -> Propagation + 'static + Send + Sync {
+		REGISTERED_HOTKEYS.request_rebind(self.0, Rebind::OnRepeat(Handler::Event(Box::new(handler))));
+	}
+
+	/// Change the release handler to one that decides whether to block the key event. Will be applied on the next update.
+	pub fn set_on_release_event<T>(&mut self, handler:T) where T:Fn(&HotkeyEvent) -> Propagation + 'static + Send + Sync {
+		REGISTERED_HOTKEYS.request_rebind(self.0, Rebind::OnRelease(Handler::Event(Box::new(handler))));
+	}
+
+	/// Remove the hotkey from the registered hotkeys. A pressed hotkey is released first. When called from an inline handler, the hotkey is removed on the next update.
+	pub fn unregister(self) {
+		REGISTERED_HOTKEYS.unregister(&[self.0]);
 	}
+
+	/// Turn the handle into a guard that unregisters the hotkey when it is dropped.
+	pub fn unregister_on_drop(self) -> HotkeyGuard {
+		HotkeyGuard(self)
+	}
+}
+
+
+
+/// Keeps a hotkey registered. The hotkey is unregistered when the guard is dropped.
+#[must_use = "the hotkey is unregistered when the guard is dropped"]
+pub struct HotkeyGuard(HotkeyHandle);
+impl Deref for HotkeyGuard {
+	type Target = HotkeyHandle;
+	fn deref(&self) -> &HotkeyHandle {
+		&self.0
+	}
+}
+impl DerefMut for HotkeyGuard {
+	fn deref_mut(&mut self) -> &mut HotkeyHandle {
+		&mut self.0
+	}
+}
+impl Drop for HotkeyGuard {
+	fn drop(&mut self) {
+		REGISTERED_HOTKEYS.unregister(&[self.0.0]);
+	}
+}
+
+
+
+/// Execute an event handler. Event handlers always execute inline, so a panicking handler passes the event instead of unwinding into the hook.
+fn handle_event(handler:&EventHandler, event:&HotkeyEvent) -> Propagation {
+	panic::catch_unwind(AssertUnwindSafe(|| handler(event))).unwrap_or(Propagation::Pass)
+}
+
+/// Combine keys into a single pattern.
+fn keys_pattern(keys:&[Key]) -> KeyPattern {
+	keys.iter().map(|key| key.pattern()).reduce(|a, b| a ^ b).unwrap_or_default()
 }
\ No newline at end of file
@@ -0,0 +1,508 @@
+#[cfg(test)]
+pub(crate) mod tests {
+	use crate::{ ActiveWindow, Hotkey, HotkeyContext, HotkeyEvent, HotkeyHandle, MatchMode, Propagation, WindowMatcher, WindowSource, clear_app_state, key_hook::simulate, keys, layers, set_app_state, set_window_source };
+	use std::{ sync::{ Arc, Mutex, MutexGuard, atomic::{ AtomicBool, AtomicUsize, Ordering } }, thread, time::{ Duration, Instant, SystemTime } };
+
+
+
+	// Registered hotkeys are shared between all tests, so tests cannot run simultaneously.
+	pub(crate) static HOTKEY_LOCK:Mutex<()> = Mutex::new(());
+
+	/// Create a counter and a handler that increments it.
+	pub(crate) fn counter() -> (Arc<AtomicUsize>, impl Fn() + Send + Sync + 'static) {
+		let counter:Arc<AtomicUsize> = Arc::new(AtomicUsize::new(0));
+		let handler_counter:Arc<AtomicUsize> = counter.clone();
+		(counter, move || { handler_counter.fetch_add(1, Ordering::SeqCst); })
+	}
+
+
+
+	#[test]
+	fn test_press_repeat_release() {
+		let _lock:MutexGuard<'_, ()> = HOTKEY_LOCK.lock().unwrap();
+		let (press_count, on_press) = counter();
+		let (repeat_count, on_repeat) = counter();
+		let (release_count, on_release) = counter();
+		Hotkey::new(&[keys::LSHIFT, keys::F13]).on_press(on_press).on_repeat(on_repeat).on_release(on_release).inline().register();
+
+		simulate(keys::F13, true);
+		assert_eq!(press_count.load(Ordering::SeqCst), 0);
+		simulate(keys::LSHIFT, true);
+		assert_eq!(press_count.load(Ordering::SeqCst), 1);
+		simulate(keys::LSHIFT, true);
+		simulate(keys::LSHIFT, true);
+		assert_eq!(repeat_count.load(Ordering::SeqCst), 2);
+		simulate(keys::F13, false);
+		assert_eq!(release_count.load(Ordering::SeqCst), 1);
+		simulate(keys::LSHIFT, false);
+		assert_eq!([press_count.load(Ordering::SeqCst), repeat_count.load(Ordering::SeqCst), release_count.load(Ordering::SeqCst)], [1, 2, 1]);
+	}
+
+	#[test]
+	fn test_blocking() {
+		let _lock:MutexGuard<'_, ()> = HOTKEY_LOCK.lock().unwrap();
+		Hotkey::new(&[keys::F14]).on_press(|| {}).blocking().inline().register();
+		Hotkey::new(&[keys::F15]).on_press(|| {}).inline().register();
+
+		assert!(simulate(keys::F14, true));
+		assert!(simulate(keys::F14, true));
+		assert!(!simulate(keys::F14, false));
+		assert!(!simulate(keys::F15, true));
+		assert!(!simulate(keys::F15, false));
+	}
+
+	#[test]
+	fn test_enable_disable() {
+		let _lock:MutexGuard<'_, ()> = HOTKEY_LOCK.lock().unwrap();
+		let (press_count, on_press) = counter();
+		let mut handle:HotkeyHandle = Hotkey::new(&[keys::F16]).on_press(on_press).disabled().inline().register();
+
+		simulate(keys::F16, true);
+		simulate(keys::F16, false);
+		assert_eq!(press_count.load(Ordering::SeqCst), 0);
+		handle.enable();
+		simulate(keys::F16, true);
+		simulate(keys::F16, false);
+		assert_eq!(press_count.load(Ordering::SeqCst), 1);
+		handle.toggle();
+		simulate(keys::F16, true);
+		simulate(keys::F16, false);
+		assert_eq!(press_count.load(Ordering::SeqCst), 1);
+	}
+
+	#[test]
+	fn test_match_mode() {
+		let _lock:MutexGuard<'_, ()> = HOTKEY_LOCK.lock().unwrap();
+		let (short_count, on_short) = counter();
+		let (long_count, on_long) = counter();
+		Hotkey::new(&[keys::RALT, keys::V]).match_mode(MatchMode::Exact).on_press(on_short).inline().register();
+		Hotkey::new(&[keys::RALT, keys::RSHIFT, keys::V]).match_mode(MatchMode::Exact).on_press(on_long).inline().register();
+
+		simulate(keys::RALT, true);
+		simulate(keys::V, true);
+		simulate(keys::V, false);
+		simulate(keys::RSHIFT, true);
+		simulate(keys::V, true);
+		simulate(keys::V, false);
+		simulate(keys::RSHIFT, false);
+		simulate(keys::RALT, false);
+		assert_eq!([short_count.load(Ordering::SeqCst), long_count.load(Ordering::SeqCst)], [1, 1]);
+	}
+
+	#[test]
+	fn test_trigger() {
+		let _lock:MutexGuard<'_, ()> = HOTKEY_LOCK.lock().unwrap();
+		let (press_count, on_press) = counter();
+		Hotkey::new(&[keys::RWIN]).trigger(keys::N).on_press(on_press).inline().register();
+
+		// Pressing the modifier last does not trigger, not even when the trigger repeats.
+		simulate(keys::N, true);
+		simulate(keys::RWIN, true);
+		simulate(keys::N, true);
+		simulate(keys::N, false);
+		assert_eq!(press_count.load(Ordering::SeqCst), 0);
+
+		// Pressing the trigger last does.
+		simulate(keys::N, true);
+		assert_eq!(press_count.load(Ordering::SeqCst), 1);
+
+		// Re-pressing the modifier does not trigger again.
+		simulate(keys::RWIN, false);
+		simulate(keys::RWIN, true);
+		simulate(keys::N, true);
+		assert_eq!(press_count.load(Ordering::SeqCst), 1);
+		simulate(keys::N, false);
+		simulate(keys::RWIN, false);
+	}
+
+	#[test]
+	fn test_tap_and_long_press() {
+		let _lock:MutexGuard<'_, ()> = HOTKEY_LOCK.lock().unwrap();
+		let (tap_count, on_tap) = counter();
+		let (long_press_count, on_long_press) = counter();
+		Hotkey::new(&[keys::NUMPAD1]).on_tap(Duration::from_millis(50), on_tap).on_long_press(Duration::from_millis(100), on_long_press).inline().register();
+
+		simulate(keys::NUMPAD1, true);
+		simulate(keys::NUMPAD1, false);
+		assert_eq!([tap_count.load(Ordering::SeqCst), long_press_count.load(Ordering::SeqCst)], [1, 0]);
+		simulate(keys::NUMPAD1, true);
+		thread::sleep(Duration::from_millis(150));
+		simulate(keys::NUMPAD1, false);
+		assert_eq!([tap_count.load(Ordering::SeqCst), long_press_count.load(Ordering::SeqCst)], [1, 1]);
+	}
+
+	#[test]
+	fn test_hold() {
+		let _lock:MutexGuard<'_, ()> = HOTKEY_LOCK.lock().unwrap();
+		let (hold_count, on_hold) = counter();
+		let (tap_count, on_tap) = counter();
+		Hotkey::new(&[keys::NUMPAD2]).on_hold(Duration::from_millis(30), on_hold).on_tap(Duration::from_secs(10), on_tap).inline().register();
+
+		// Releasing before the hold duration does not trigger the hold handler.
+		simulate(keys::NUMPAD2, true);
+		simulate(keys::NUMPAD2, false);
+		thread::sleep(Duration::from_millis(60));
+		assert_eq!([hold_count.load(Ordering::SeqCst), tap_count.load(Ordering::SeqCst)], [0, 1]);
+
+		// Holding triggers without any further input, and the release is no longer a tap.
+		simulate(keys::NUMPAD2, true);
+		let start:Instant = Instant::now();
+		while hold_count.load(Ordering::SeqCst) == 0 && start.elapsed() < Duration::from_secs(2) {
+			thread::sleep(Duration::from_millis(5));
+		}
+		simulate(keys::NUMPAD2, false);
+		assert_eq!([hold_count.load(Ordering::SeqCst), tap_count.load(Ordering::SeqCst)], [1, 1]);
+	}
+
+	#[test]
+	fn test_double_tap() {
+		let _lock:MutexGuard<'_, ()> = HOTKEY_LOCK.lock().unwrap();
+		let (tap_count, on_tap) = counter();
+		let (double_tap_count, on_double_tap) = counter();
+		Hotkey::new(&[keys::NUMPAD3]).on_tap(Duration::from_millis(500), on_tap).on_double_tap(Duration::from_millis(500), on_double_tap).inline().register();
+
+		for _ in 0..3 {
+			simulate(keys::NUMPAD3, true);
+			simulate(keys::NUMPAD3, false);
+		}
+		assert_eq!([tap_count.load(Ordering::SeqCst), double_tap_count.load(Ordering::SeqCst)], [2, 1]);
+	}
+
+	#[test]
+	fn test_double_tap_after_hold() {
+		let _lock:MutexGuard<'_, ()> = HOTKEY_LOCK.lock().unwrap();
+		let (double_tap_count, on_double_tap) = counter();
+		Hotkey::new(&[keys::BROWSER_REFRESH]).on_double_tap(Duration::from_millis(50), on_double_tap).inline().register();
+
+		simulate(keys::BROWSER_REFRESH, true);
+		thread::sleep(Duration::from_millis(100));
+		simulate(keys::BROWSER_REFRESH, false);
+		simulate(keys::BROWSER_REFRESH, true);
+		simulate(keys::BROWSER_REFRESH, false);
+		assert_eq!(double_tap_count.load(Ordering::SeqCst), 0);
+		simulate(keys::BROWSER_REFRESH, true);
+		simulate(keys::BROWSER_REFRESH, false);
+		assert_eq!(double_tap_count.load(Ordering::SeqCst), 1);
+	}
+
+	#[test]
+	fn test_gesture_blocking() {
+		let _lock:MutexGuard<'_, ()> = HOTKEY_LOCK.lock().unwrap();
+		Hotkey::new(&[keys::NUMPAD4]).on_tap(Duration::from_millis(500), || {}).blocking().inline().register();
+
+		assert!(simulate(keys::NUMPAD4, true));
+		assert!(simulate(keys::NUMPAD4, true));
+		assert!(simulate(keys::NUMPAD4, false));
+	}
+
+	#[test]
+	fn test_sequence() {
+		let _lock:MutexGuard<'_, ()> = HOTKEY_LOCK.lock().unwrap();
+		let (press_count, on_press) = counter();
+		let (release_count, on_release) = counter();
+		Hotkey::sequence(&[&[keys::RCONTROL, keys::K], &[keys::RCONTROL, keys::C]]).on_press(on_press).on_release(on_release).inline().register();
+
+		simulate(keys::RCONTROL, true);
+		simulate(keys::C, true);
+		simulate(keys::C, false);
+		assert_eq!(press_count.load(Ordering::SeqCst), 0);
+		simulate(keys::K, true);
+		simulate(keys::K, true);
+		simulate(keys::K, false);
+		assert_eq!(press_count.load(Ordering::SeqCst), 0);
+		simulate(keys::C, true);
+		assert_eq!(press_count.load(Ordering::SeqCst), 1);
+		simulate(keys::C, false);
+		assert_eq!(release_count.load(Ordering::SeqCst), 1);
+
+		// The sequence starts over after it triggered.
+		simulate(keys::C, true);
+		simulate(keys::C, false);
+		simulate(keys::RCONTROL, false);
+		assert_eq!(press_count.load(Ordering::SeqCst), 1);
+	}
+
+	#[test]
+	fn test_sequence_interrupted() {
+		let _lock:MutexGuard<'_, ()> = HOTKEY_LOCK.lock().unwrap();
+		let (press_count, on_press) = counter();
+		Hotkey::sequence(&[&[keys::G], &[keys::G]]).on_press(on_press).inline().register();
+
+		for key in [keys::G, keys::X, keys::G] {
+			simulate(key, true);
+			simulate(key, false);
+		}
+		assert_eq!(press_count.load(Ordering::SeqCst), 0);
+		simulate(keys::G, true);
+		simulate(keys::G, false);
+		assert_eq!(press_count.load(Ordering::SeqCst), 1);
+	}
+
+	#[test]
+	fn test_sequence_timeout() {
+		let _lock:MutexGuard<'_, ()> = HOTKEY_LOCK.lock().unwrap();
+		let (press_count, on_press) = counter();
+		Hotkey::sequence(&[&[keys::H], &[keys::H]]).step_timeout(Duration::from_millis(20)).on_press(on_press).inline().register();
+
+		simulate(keys::H, true);
+		simulate(keys::H, false);
+		thread::sleep(Duration::from_millis(50));
+		simulate(keys::H, true);
+		simulate(keys::H, false);
+		assert_eq!(press_count.load(Ordering::SeqCst), 0);
+		simulate(keys::H, true);
+		simulate(keys::H, false);
+		assert_eq!(press_count.load(Ordering::SeqCst), 1);
+	}
+
+	#[test]
+	fn test_sequence_blocking() {
+		let _lock:MutexGuard<'_, ()> = HOTKEY_LOCK.lock().unwrap();
+		Hotkey::sequence(&[&[keys::J], &[keys::L]]).on_press(|| {}).blocking().inline().register();
+
+		assert!(!simulate(keys::L, true));
+		assert!(!simulate(keys::L, false));
+		assert!(simulate(keys::J, true));
+		assert!(simulate(keys::J, false));
+		assert!(simulate(keys::L, true));
+		assert!(!simulate(keys::L, false));
+	}
+
+	#[test]
+	fn test_predicate_context() {
+		let _lock:MutexGuard<'_, ()> = HOTKEY_LOCK.lock().unwrap();
+		let (press_count, on_press) = counter();
+		let (release_count, on_release) = counter();
+		Hotkey::new(&[keys::F12]).when(|context:&HotkeyContext| context.is_held(keys::NUMPAD0) || context.is_layer_active("predicate")).on_press(on_press).on_release(on_release).blocking().inline().register();
+
+		// The predicate prevents pressing and blocking.
+		assert!(!simulate(keys::F12, true));
+		assert!(!simulate(keys::F12, false));
+		simulate(keys::NUMPAD0, true);
+		assert!(simulate(keys::F12, true));
+		simulate(keys::NUMPAD0, false);
+		simulate(keys::F12, false);
+		layers::activate("predicate");
+		simulate(keys::F12, true);
+		layers::deactivate("predicate");
+		simulate(keys::F12, false);
+		assert_eq!([press_count.load(Ordering::SeqCst), release_count.load(Ordering::SeqCst)], [2, 2]);
+	}
+
+	#[test]
+	fn test_predicate_app_state() {
+		#[derive(PartialEq)]
+		enum AppMode { Normal, Insert }
+
+		let _lock:MutexGuard<'_, ()> = HOTKEY_LOCK.lock().unwrap();
+		let (normal_count, on_normal_press) = counter();
+		let (insert_count, on_insert_press) = counter();
+		Hotkey::new(&[keys::DECIMAL]).when(|context:&HotkeyContext| context.app_state::<AppMode>() == Some(&AppMode::Normal)).on_press(on_normal_press).inline().register();
+		Hotkey::new(&[keys::DECIMAL]).when(|context:&HotkeyContext| context.app_state::<AppMode>() == Some(&AppMode::Insert)).on_press(on_insert_press).inline().register();
+
+		simulate(keys::DECIMAL, true);
+		simulate(keys::DECIMAL, false);
+		set_app_state(AppMode::Normal);
+		simulate(keys::DECIMAL, true);
+		simulate(keys::DECIMAL, false);
+		set_app_state(AppMode::Insert);
+		simulate(keys::DECIMAL, true);
+		simulate(keys::DECIMAL, false);
+		clear_app_state();
+		assert_eq!([normal_count.load(Ordering::SeqCst), insert_count.load(Ordering::SeqCst)], [1, 1]);
+	}
+
+	#[test]
+	fn test_window_scope() {
+		struct TestWindowSource(Arc<Mutex<Option<ActiveWindow>>>);
+		impl WindowSource for TestWindowSource {
+			fn active_window(&self) -> Option<ActiveWindow> { self.0.lock().unwrap().clone() }
+		}
+
+		let _lock:MutexGuard<'_, ()> = HOTKEY_LOCK.lock().unwrap();
+		let active_window:Arc<Mutex<Option<ActiveWindow>>> = Arc::new(Mutex::new(None));
+		set_window_source(TestWindowSource(active_window.clone()));
+		let (press_count, on_press) = counter();
+		Hotkey::new(&[keys::SEPARATOR]).window(WindowMatcher::class("*term*")).window(WindowMatcher::title("* - Vim")).on_press(on_press).inline().register();
+
+		for window in [None, Some(ActiveWindow::new("firefox", "Navigator", "Vim")), Some(ActiveWindow::new("xterm", "xterm", "~")), Some(ActiveWindow::new("gvim", "gvim", "main.rs - Vim"))] {
+			*active_window.lock().unwrap() = window;
+			simulate(keys::SEPARATOR, true);
+			simulate(keys::SEPARATOR, false);
+		}
+		assert_eq!(press_count.load(Ordering::SeqCst), 2);
+	}
+
+	#[test]
+	fn test_event_propagation() {
+		let _lock:MutexGuard<'_, ()> = HOTKEY_LOCK.lock().unwrap();
+		let swallow:Arc<AtomicBool> = Arc::new(AtomicBool::new(true));
+		let handler_swallow:Arc<AtomicBool> = swallow.clone();
+		Hotkey::new(&[keys::INSERT]).on_press_event(move |_| if handler_swallow.load(Ordering::SeqCst) { Propagation::Block } else { Propagation::Pass }).register();
+
+		// Repeats without their own handler follow the decision of the press, releases without a handler are not blocked.
+		assert!(simulate(keys::INSERT, true));
+		assert!(simulate(keys::INSERT, true));
+		assert!(!simulate(keys::INSERT, false));
+		swallow.store(false, Ordering::SeqCst);
+		assert!(!simulate(keys::INSERT, true));
+		assert!(!simulate(keys::INSERT, true));
+		assert!(!simulate(keys::INSERT, false));
+	}
+
+	#[test]
+	fn test_event_contents() {
+		let _lock:MutexGuard<'_, ()> = HOTKEY_LOCK.lock().unwrap();
+		let events:Arc<Mutex<Vec<HotkeyEvent>>> = Arc::new(Mutex::new(Vec::new()));
+		let handler_events:Arc<Mutex<Vec<HotkeyEvent>>> = events.clone();
+		Hotkey::new(&[keys::SCROLL, keys::CLEAR]).on_repeat_event(move |event| {
+			handler_events.lock().unwrap().push(event.clone());
+			Propagation::Pass
+		}).register();
+
+		let start:SystemTime = SystemTime::now();
+		simulate(keys::SCROLL, true);
+		simulate(keys::CLEAR, true);
+		simulate(keys::CLEAR, true);
+		simulate(keys::CLEAR, true);
+		simulate(keys::CLEAR, false);
+		simulate(keys::SCROLL, false);
+		let events:Vec<HotkeyEvent> = events.lock().unwrap().clone();
+		assert_eq!(events.iter().map(|event| event.repeat_count).collect::<Vec<u32>>(), vec![1, 2]);
+		assert!(events.iter().all(|event| event.key == keys::CLEAR && event.held_keys == keys::SCROLL.pattern() | keys::CLEAR && event.timestamp >= start));
+	}
+
+	#[test]
+	fn test_set_keys() {
+		let _lock:MutexGuard<'_, ()> = HOTKEY_LOCK.lock().unwrap();
+		let (press_count, on_press) = counter();
+		let mut handle:HotkeyHandle = Hotkey::new(&[keys::SNAPSHOT]).on_press(on_press).inline().register();
+
+		simulate(keys::SNAPSHOT, true);
+		simulate(keys::SNAPSHOT, false);
+		handle.set_keys(&[keys::LSHIFT, keys::SLEEP]);
+		simulate(keys::SNAPSHOT, true);
+		simulate(keys::SNAPSHOT, false);
+		simulate(keys::SLEEP, true);
+		simulate(keys::SLEEP, false);
+		simulate(keys::LSHIFT, true);
+		simulate(keys::SLEEP, true);
+		simulate(keys::SLEEP, false);
+		simulate(keys::LSHIFT, false);
+		assert_eq!(press_count.load(Ordering::SeqCst), 2);
+	}
+
+	#[test]
+	fn test_set_keys_while_held() {
+		let _lock:MutexGuard<'_, ()> = HOTKEY_LOCK.lock().unwrap();
+		let (press_count, on_press) = counter();
+		let (release_count, on_release) = counter();
+		let mut handle:HotkeyHandle = Hotkey::new(&[keys::FINAL]).on_press(on_press).on_release(on_release).inline().register();
+
+		// The held hotkey is released when its keys change, so the new keys press it again.
+		simulate(keys::FINAL, true);
+		handle.set_keys(&[keys::JUNJA]);
+		simulate(keys::FINAL, false);
+		assert_eq!(release_count.load(Ordering::SeqCst), 1);
+		simulate(keys::JUNJA, true);
+		simulate(keys::JUNJA, false);
+		assert_eq!([press_count.load(Ordering::SeqCst), release_count.load(Ordering::SeqCst)], [2, 2]);
+		handle.unregister();
+	}
+
+	#[test]
+	fn test_set_keys_rebinding_release() {
+		let _lock:MutexGuard<'_, ()> = HOTKEY_LOCK.lock().unwrap();
+		let (press_count, on_press) = counter();
+		let other_handle:HotkeyHandle = Hotkey::new(&[keys::KEY_6]).on_press(on_press).inline().register();
+		let rebound_handle:HotkeyHandle = other_handle.clone();
+		let mut handle:HotkeyHandle = Hotkey::new(&[keys::KANA]).on_release(move || rebound_handle.clone().set_keys(&[keys::KEY_4])).inline().register();
+
+		// The release handler of a rebound hotkey can rebind other hotkeys.
+		simulate(keys::KANA, true);
+		handle.set_keys(&[keys::KEY_7]);
+		simulate(keys::KANA, false);
+		simulate(keys::KEY_4, true);
+		simulate(keys::KEY_4, false);
+		handle.unregister();
+		other_handle.unregister();
+		assert_eq!(press_count.load(Ordering::SeqCst), 1);
+	}
+
+	#[test]
+	fn test_set_handlers() {
+		let _lock:MutexGuard<'_, ()> = HOTKEY_LOCK.lock().unwrap();
+		let (first_count, on_first_press) = counter();
+		let (second_count, on_second_press) = counter();
+		let mut handle:HotkeyHandle = Hotkey::new(&[keys::BROWSER_STOP]).on_press(on_first_press).inline().register();
+
+		assert!(!simulate(keys::BROWSER_STOP, true));
+		simulate(keys::BROWSER_STOP, false);
+		handle.set_on_press(on_second_press);
+		simulate(keys::BROWSER_STOP, true);
+		simulate(keys::BROWSER_STOP, false);
+		handle.set_on_press_event(|_| Propagation::Block);
+		assert!(simulate(keys::BROWSER_STOP, true));
+		simulate(keys::BROWSER_STOP, false);
+		assert_eq!([first_count.load(Ordering::SeqCst), second_count.load(Ordering::SeqCst)], [1, 1]);
+	}
+
+	#[test]
+	fn test_panicking_handlers() {
+		let _lock:MutexGuard<'_, ()> = HOTKEY_LOCK.lock().unwrap();
+		let (press_count, on_press) = counter();
+		Hotkey::new(&[keys::BROWSER_BACK]).on_press(|| panic!("Panicking handler")).inline().register();
+		Hotkey::new(&[keys::BROWSER_FORWARD]).when(|_| panic!("Panicking predicate")).blocking().register();
+		Hotkey::new(&[keys::BROWSER_HOME]).on_press(on_press).inline().register();
+		Hotkey::new(&[keys::BROWSER_SEARCH]).on_press_event(|_| panic!("Panicking event handler")).register();
+
+		// Panics are caught, a panicking event handler passes the event and a panicking predicate does not allow the hotkey.
+		simulate(keys::BROWSER_BACK, true);
+		simulate(keys::BROWSER_BACK, false);
+		assert!(!simulate(keys::BROWSER_SEARCH, true));
+		simulate(keys::BROWSER_SEARCH, false);
+		assert!(!simulate(keys::BROWSER_FORWARD, true));
+		simulate(keys::BROWSER_FORWARD, false);
+		simulate(keys::BROWSER_HOME, true);
+		simulate(keys::BROWSER_HOME, false);
+		assert_eq!(press_count.load(Ordering::SeqCst), 1);
+	}
+
+	#[cfg(feature="async")]
+	#[test]
+	fn test_wait_pressed() {
+		let _lock:MutexGuard<'_, ()> = HOTKEY_LOCK.lock().unwrap();
+		let (press_count, on_press) = counter();
+		let waiter:thread::JoinHandle<()> = thread::spawn(|| futures::executor::block_on(Hotkey::new(&[keys::F11]).on_press(on_press).inline().wait_pressed()));
+
+		// The hotkey is registered once the waiting thread starts polling, so keep pressing until it finishes.
+		let start:Instant = Instant::now();
+		while !waiter.is_finished() && start.elapsed() < Duration::from_secs(2) {
+			simulate(keys::F11, true);
+			simulate(keys::F11, false);
+			thread::sleep(Duration::from_millis(5));
+		}
+		assert!(waiter.is_finished());
+		assert_eq!(press_count.load(Ordering::SeqCst), 1);
+
+		// The hotkey is unregistered after being pressed.
+		simulate(keys::F11, true);
+		simulate(keys::F11, false);
+		assert_eq!(press_count.load(Ordering::SeqCst), 1);
+	}
+
+	#[cfg(feature="async")]
+	#[test]
+	fn test_wait_pressed_cancelled() {
+		let _lock:MutexGuard<'_, ()> = HOTKEY_LOCK.lock().unwrap();
+		let (press_count, on_press) = counter();
+		futures::executor::block_on(async {
+			let mut waiter = Box::pin(Hotkey::new(&[keys::F4]).on_press(on_press).inline().wait_pressed());
+			assert!(futures::poll!(waiter.as_mut()).is_pending());
+		});
+
+		simulate(keys::F4, true);
+		simulate(keys::F4, false);
+		assert_eq!(press_count.load(Ordering::SeqCst), 0);
+	}
+}
\ No newline at end of file
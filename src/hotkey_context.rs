@@ -0,0 +1,88 @@
+use std::{ any::Any, cell::OnceCell, sync::{ Arc, RwLock }, time::SystemTime };
+use crate::{ ActiveWindow, Key, KeyPattern };
+
+
+
+static APP_STATE:RwLock<Option<Arc<dyn Any + Send + Sync>>> = RwLock::new(None);
+
+
+
+/// The context hotkey predicates are evaluated in. Contains the held keys, the active layers, the active window and the state set by the application.
+pub struct HotkeyContext {
+	timestamp:SystemTime,
+	held_keys:KeyPattern,
+	active_layers:Vec<String>,
+	app_state:Option<Arc<dyn Any + Send + Sync>>,
+	active_window:OnceCell<Option<ActiveWindow>> // Only read from the window source when requested.
+}
+impl HotkeyContext {
+
+	/* CONSTRUCTOR METHODS */
+
+	/// Create a new context for a key event with the current application state.
+	pub(crate) fn new(timestamp:SystemTime, held_keys:KeyPattern, active_layers:Vec<String>) -> HotkeyContext {
+		HotkeyContext {
+			timestamp,
+			held_keys,
+			active_layers,
+			app_state: APP_STATE.read().unwrap().clone(),
+			active_window: OnceCell::new()
+		}
+	}
+
+
+
+	/* PROPERTY GETTER METHODS */
+
+	/// Get the time of the key event the hotkeys are updated for.
+	pub fn timestamp(&self) -> SystemTime {
+		self.timestamp
+	}
+
+	/// Get a pattern of all physically held keys.
+	pub fn held_keys(&self) -> KeyPattern {
+		self.held_keys
+	}
+
+	/// Check if a key is physically held.
+	pub fn is_held(&self, key:Key) -> bool {
+		self.held_keys & key.pattern() != KeyPattern::ZERO
+	}
+
+	/// Get the active layer on top of the layer stack.
+	pub fn active_layer(&self) -> Option<&str> {
+		self.active_layers.last().map(|layer| layer.as_str())
+	}
+
+	/// Get all active layers, from the bottom of the layer stack to the top.
+	pub fn active_layers(&self) -> &[String] {
+		&self.active_layers
+	}
+
+	/// Check if a layer is active.
+	pub fn is_layer_active(&self, layer:&str) -> bool {
+		self.active_layers.iter().any(|active_layer| active_layer == layer)
+	}
+
+	/// Get the window that has focus, if the window source can determine it.
+	pub fn active_window(&self) -> Option<&ActiveWindow> {
+		self.active_window.get_or_init(crate::active_window).as_ref()
+	}
+
+	/// Get the state set by the application, if it was set and is of the requested type.
+	pub fn app_state<T>(&self) -> Option<&T> where T:Any {
+		self.app_state.as_ref().and_then(|state| state.downcast_ref::<T>())
+	}
+}
+
+
+
+/// Set the application-defined state hotkey predicates can read from their context. Replaces the previous state, which may be of a different type.
+pub fn set_app_state<T>(state:T) where T:Any + Send + Sync {
+	*APP_STATE.write().unwrap() = Some(Arc::new(state));
+}
+
+/// Remove the application-defined state.
+pub fn clear_app_state() {
+	*APP_STATE.write().unwrap() = None;
+}
\ No newline at end of file
@@ -0,0 +1,24 @@
+use std::time::SystemTime;
+use crate::{ HotkeyHandle, Key, KeyPattern };
+
+
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Propagation { Block, Pass }
+
+
+
+/// The key event that triggered a hotkey handler.
+#[derive(Clone, Debug)]
+pub struct HotkeyEvent {
+	/// The key whose event triggered the handler. Key code 0 when the hotkey is released because it was unregistered or its keys changed.
+	pub key:Key,
+	/// The time of the key event.
+	pub timestamp:SystemTime,
+	/// The number of repeats since the hotkey was pressed. Zero for the press itself.
+	pub repeat_count:u32,
+	/// All physically held keys.
+	pub held_keys:KeyPattern,
+	/// The handle of the hotkey, to modify or unregister it from the handler.
+	pub handle:HotkeyHandle
+}
\ No newline at end of file
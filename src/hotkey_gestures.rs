@@ -0,0 +1,98 @@
+use std::time::{ Duration, Instant };
+use crate::{ handler_executor::{ HandlerDispatcher, HotkeyHandler }, hotkey_registry::REGISTERED_HOTKEYS, hotkey_timer::HOTKEY_TIMER };
+
+
+
+/// Detects taps, holds, double-taps and long presses of a hotkey from the moments it is pressed and released.
+#[derive(Default)]
+pub(crate) struct HotkeyGestures {
+	pub(crate) on_tap:Option<(Duration, HotkeyHandler)>,
+	pub(crate) on_hold:Option<(Duration, HotkeyHandler)>,
+	pub(crate) on_double_tap:Option<(Duration, HotkeyHandler)>,
+	pub(crate) on_long_press:Option<(Duration, HotkeyHandler)>,
+
+	press_time:Option<Instant>,
+	last_tap_time:Option<Instant>,
+	generation:u64,
+	hold_executed:bool,
+	double_tap_executed:bool
+}
+impl HotkeyGestures {
+
+	/* PROPERTY GETTER METHODS */
+
+	/// Whether or not any gesture handler is set.
+	pub(crate) fn any(&self) -> bool {
+		self.on_tap.is_some() || self.on_hold.is_some() || self.on_double_tap.is_some() || self.on_long_press.is_some()
+	}
+
+
+
+	/* USAGE METHODS */
+
+	/// Handle the hotkey with the given id being pressed. Returns true if any gesture handler is set, as the press is part of a gesture.
+	pub(crate) fn press(&mut self, hotkey_id:u64, dispatcher:&HandlerDispatcher) -> bool {
+		if !self.any() {
+			return false;
+		}
+		let now:Instant = Instant::now();
+		self.press_time = Some(now);
+		self.generation += 1;
+		self.hold_executed = false;
+		self.double_tap_executed = false;
+
+		// Schedule the hold timer.
+		if let Some((hold_duration, _)) = &self.on_hold {
+			let generation:u64 = self.generation;
+			HOTKEY_TIMER.schedule(now + *hold_duration, move || REGISTERED_HOTKEYS.timer_expired(hotkey_id, generation));
+		}
+
+		// Pressing again shortly after a tap is a double-tap.
+		if let Some((window, handler)) = &self.on_double_tap && let Some(last_tap_time) = self.last_tap_time.take() && now - last_tap_time <= *window {
+			dispatcher.dispatch(handler);
+			self.double_tap_executed = true;
+		}
+		true
+	}
+
+	/// Handle the hotkey being released. Returns true if any gesture handler is set, as the release is part of a gesture.
+	pub(crate) fn release(&mut self, dispatcher:&HandlerDispatcher) -> bool {
+		let now:Instant = Instant::now();
+		let Some(press_time) = self.press_time.take() else {
+			return false;
+		};
+		let press_duration:Duration = now - press_time;
+		if self.hold_executed || self.double_tap_executed {
+			return true;
+		}
+
+		// Releasing after a long time is a long press.
+		if let Some((long_press_duration, handler)) = &self.on_long_press && press_duration >= *long_press_duration {
+			dispatcher.dispatch(handler);
+			return true;
+		}
+
+		// Releasing quickly is a tap. Without a tap handler, presses up to the double-tap window count as taps.
+		match &self.on_tap {
+			Some((tap_duration, handler)) if press_duration <= *tap_duration => {
+				self.last_tap_time = Some(now);
+				dispatcher.dispatch(handler);
+			},
+			Some(_) => {},
+			None => {
+				if let Some((window, _)) = &self.on_double_tap && press_duration <= *window {
+					self.last_tap_time = Some(now);
+				}
+			}
+		}
+		true
+	}
+
+	/// Handle a timer of the hotkey expiring. Executes the hold handler if the hotkey is still held since the timer was scheduled.
+	pub(crate) fn timer_expired(&mut self, generation:u64, dispatcher:&HandlerDispatcher) {
+		if generation == self.generation && self.press_time.is_some() && !self.hold_executed && !self.double_tap_executed && let Some((_, handler)) = &self.on_hold {
+			dispatcher.dispatch(handler);
+			self.hold_executed = true;
+		}
+	}
+}
\ No newline at end of file
@@ -0,0 +1,105 @@
+use crate::{ Hotkey, HotkeyHandle, hokey::ModificationRequest, hotkey_registry::REGISTERED_HOTKEYS };
+
+
+
+/// A set of hotkeys that are registered and unregistered together. Key events never see only part of the set registered.
+#[derive(Default)]
+pub struct HotkeyGroup {
+	hotkeys:Vec<Hotkey>
+}
+impl HotkeyGroup {
+
+	/* CONSTRUCTOR METHODS */
+
+	/// Create a new, empty group.
+	pub fn new() -> HotkeyGroup {
+		HotkeyGroup::default()
+	}
+
+	/// Return self with an additional hotkey.
+	pub fn with(mut self, hotkey:Hotkey) -> Self {
+		self.hotkeys.push(hotkey);
+		self
+	}
+
+	/// Add a hotkey to the group.
+	pub fn add(&mut self, hotkey:Hotkey) {
+		self.hotkeys.push(hotkey);
+	}
+
+
+
+	/* REGISTERED STATIC METHODS */
+
+	/// Register all hotkeys in the group at once.
+	pub fn register(mut self) -> HotkeyGroupHandle {
+		let handles:Vec<HotkeyHandle> = self.hotkeys.iter_mut().map(|hotkey| hotkey.mark_registered()).collect();
+		REGISTERED_HOTKEYS.register(self.hotkeys);
+		HotkeyGroupHandle { handles, unregister_on_drop: false }
+	}
+}
+
+
+
+/// A handle to a registered group of hotkeys. Modifications apply to all hotkeys in the group at once.
+pub struct HotkeyGroupHandle {
+	handles:Vec<HotkeyHandle>,
+	unregister_on_drop:bool
+}
+impl HotkeyGroupHandle {
+
+	/* PROPERTY GETTER METHODS */
+
+	/// Get the handles of the individual hotkeys in the group.
+	pub fn handles(&self) -> &[HotkeyHandle] {
+		&self.handles
+	}
+
+
+
+	/* USAGE METHODS */
+
+	/// Create a request to modify all hotkeys in the group. Will be applied on the next update.
+	fn request_modification(&self, modification:ModificationRequest) {
+		REGISTERED_HOTKEYS.request_modification(&self.ids(), modification);
+	}
+
+	/// Get the ids of all hotkeys in the group.
+	fn ids(&self) -> Vec<u64> {
+		self.handles.iter().map(|handle| handle.id()).collect()
+	}
+
+	/// Enable all hotkeys in the group.
+	pub fn enable(&mut self) {
+		self.request_modification(ModificationRequest::Enable);
+	}
+
+	/// Disable all hotkeys in the group.
+	pub fn disable(&mut self) {
+		self.request_modification(ModificationRequest::Disable);
+	}
+
+	/// Toggle all hotkeys in the group.
+	pub fn toggle(&mut self) {
+		self.request_modification(ModificationRequest::Toggle);
+	}
+
+	/// Remove all hotkeys in the group from the registered hotkeys at once. Pressed hotkeys are released first.
+	pub fn unregister(mut self) {
+		REGISTERED_HOTKEYS.unregister(&self.ids());
+		self.unregister_on_drop = false;
+	}
+
+	/// Return self unregistering all hotkeys in the group when dropped.
+	pub fn unregister_on_drop(mut self) -> Self {
+		self.unregister_on_drop = true;
+		self
+	}
+}
+impl Drop for HotkeyGroupHandle {
+	fn drop(&mut self) {
+		if self.unregister_on_drop {
+			REGISTERED_HOTKEYS.unregister(&self.ids());
+		}
+	}
+}
\ No newline at end of file
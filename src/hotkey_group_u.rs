@@ -0,0 +1,119 @@
+#[cfg(test)]
+mod tests {
+	use crate::{ Hotkey, HotkeyGroup, HotkeyGroupHandle, HotkeyGuard, HotkeyHandle, Key, hokey_u::tests::{ HOTKEY_LOCK, counter }, key_hook::simulate, keys };
+	use std::sync::{ MutexGuard, atomic::Ordering };
+
+
+
+	/// Simulate pressing and releasing a key.
+	fn tap(key:Key) {
+		simulate(key, true);
+		simulate(key, false);
+	}
+
+
+
+	#[test]
+	fn test_unregister() {
+		let _lock:MutexGuard<'_, ()> = HOTKEY_LOCK.lock().unwrap();
+		let (press_count, on_press) = counter();
+		let handle:HotkeyHandle = Hotkey::new(&[keys::SELECT]).on_press(on_press).inline().register();
+
+		tap(keys::SELECT);
+		handle.unregister();
+		tap(keys::SELECT);
+		assert_eq!(press_count.load(Ordering::SeqCst), 1);
+	}
+
+	#[test]
+	fn test_unregister_held() {
+		let _lock:MutexGuard<'_, ()> = HOTKEY_LOCK.lock().unwrap();
+		let (release_count, on_release) = counter();
+		let handle:HotkeyHandle = Hotkey::new(&[keys::CONVERT]).on_release(on_release).inline().register();
+
+		// Unregistering a pressed hotkey releases it.
+		simulate(keys::CONVERT, true);
+		handle.unregister();
+		assert_eq!(release_count.load(Ordering::SeqCst), 1);
+		simulate(keys::CONVERT, false);
+		assert_eq!(release_count.load(Ordering::SeqCst), 1);
+	}
+
+	#[test]
+	fn test_unregister_held_modifying() {
+		let _lock:MutexGuard<'_, ()> = HOTKEY_LOCK.lock().unwrap();
+		let (press_count, on_press) = counter();
+		let other_handle:HotkeyHandle = Hotkey::new(&[keys::MODECHANGE]).on_press(on_press).inline().register();
+		let disabled_handle:HotkeyHandle = other_handle.clone();
+		let handle:HotkeyHandle = Hotkey::new(&[keys::ACCEPT]).on_release(move || disabled_handle.clone().disable()).inline().register();
+
+		// The release handler of an unregistered hotkey can modify other hotkeys.
+		simulate(keys::ACCEPT, true);
+		handle.unregister();
+		simulate(keys::ACCEPT, false);
+		tap(keys::MODECHANGE);
+		other_handle.unregister();
+		assert_eq!(press_count.load(Ordering::SeqCst), 0);
+	}
+
+	#[test]
+	fn test_unregister_on_drop() {
+		let _lock:MutexGuard<'_, ()> = HOTKEY_LOCK.lock().unwrap();
+		let (press_count, on_press) = counter();
+		let mut guard:HotkeyGuard = Hotkey::new(&[keys::PRINT]).on_press(on_press).inline().register().unregister_on_drop();
+
+		tap(keys::PRINT);
+		guard.disable();
+		tap(keys::PRINT);
+		guard.enable();
+		tap(keys::PRINT);
+		drop(guard);
+		tap(keys::PRINT);
+		assert_eq!(press_count.load(Ordering::SeqCst), 2);
+	}
+
+	#[test]
+	fn test_group() {
+		let _lock:MutexGuard<'_, ()> = HOTKEY_LOCK.lock().unwrap();
+		let (execute_count, on_execute) = counter();
+		let (help_count, on_help) = counter();
+		let mut group:HotkeyGroupHandle = HotkeyGroup::new().with(Hotkey::new(&[keys::EXECUTE]).on_press(on_execute).inline()).with(Hotkey::new(&[keys::HELP]).on_press(on_help).inline()).register();
+		assert_eq!(group.handles().len(), 2);
+
+		tap(keys::EXECUTE);
+		tap(keys::HELP);
+		group.disable();
+		tap(keys::EXECUTE);
+		tap(keys::HELP);
+		group.enable();
+		tap(keys::EXECUTE);
+		group.unregister();
+		tap(keys::EXECUTE);
+		tap(keys::HELP);
+		assert_eq!([execute_count.load(Ordering::SeqCst), help_count.load(Ordering::SeqCst)], [2, 1]);
+	}
+
+	#[test]
+	fn test_group_unregister_on_drop() {
+		let _lock:MutexGuard<'_, ()> = HOTKEY_LOCK.lock().unwrap();
+		let (press_count, on_press) = counter();
+		let group:HotkeyGroupHandle = HotkeyGroup::new().with(Hotkey::new(&[keys::APPS]).on_press(on_press).inline()).register().unregister_on_drop();
+
+		tap(keys::APPS);
+		drop(group);
+		tap(keys::APPS);
+		assert_eq!(press_count.load(Ordering::SeqCst), 1);
+	}
+
+	#[test]
+	fn test_group_unregister_held() {
+		let _lock:MutexGuard<'_, ()> = HOTKEY_LOCK.lock().unwrap();
+		let (release_count, on_release) = counter();
+		let group:HotkeyGroupHandle = HotkeyGroup::new().with(Hotkey::new(&[keys::NONCONVERT]).on_release(on_release).inline()).register();
+
+		simulate(keys::NONCONVERT, true);
+		group.unregister();
+		simulate(keys::NONCONVERT, false);
+		assert_eq!(release_count.load(Ordering::SeqCst), 1);
+	}
+}
\ No newline at end of file
@@ -0,0 +1,180 @@
+use std::{ cell::Cell, collections::HashMap, mem, sync::{ Mutex, MutexGuard, PoisonError }, time::SystemTime };
+use crate::{ HotkeyContext, KeyPattern, hokey::{ Hotkey, ModificationRequest, Rebind }, hotkeys::HotkeyInfo, layers };
+
+
+
+pub(crate) static REGISTERED_HOTKEYS:HotkeyRegistry = HotkeyRegistry::new();
+
+thread_local! {
+	static UPDATING:Cell<bool> = const { Cell::new(false) };
+}
+
+
+
+/// The list of registered hotkeys. Hotkeys are only mutated while updating, which only one thread can do at a time. Other threads request modifications, which are applied on the next update.
+pub(crate) struct HotkeyRegistry {
+	hotkeys:Mutex<Vec<Hotkey>>,
+	modifications:Mutex<Vec<(u64, ModificationRequest)>>,
+	rebinds:Mutex<Vec<(u64, Rebind)>>,
+	update_lock:Mutex<()>
+}
+impl HotkeyRegistry {
+
+	/* CONSTRUCTOR METHODS */
+
+	/// Create a new, empty registry.
+	pub(crate) const fn new() -> HotkeyRegistry {
+		HotkeyRegistry {
+			hotkeys: Mutex::new(Vec::new()),
+			modifications: Mutex::new(Vec::new()),
+			rebinds: Mutex::new(Vec::new()),
+			update_lock: Mutex::new(())
+		}
+	}
+
+
+
+	/* USAGE METHODS */
+
+	/// Add hotkeys to the registry at once. Replaces existing hotkeys if they were already registered.
+	pub(crate) fn register(&self, new_hotkeys:Vec<Hotkey>) {
+		let mut hotkeys:MutexGuard<'_, Vec<Hotkey>> = self.hotkeys.lock().unwrap();
+		for hotkey in new_hotkeys {
+			if let Some(existing_index) = hotkeys.iter().position(|existing_hotkey| existing_hotkey == &hotkey) {
+				hotkeys[existing_index] = hotkey;
+			} else {
+				hotkeys.push(hotkey);
+			}
+		}
+	}
+
+	/// Create a request to modify the hotkeys with the given ids at once. Will be applied on the next update.
+	pub(crate) fn request_modification(&self, hotkey_ids:&[u64], modification:ModificationRequest) {
+		self.modifications.lock().unwrap().extend(hotkey_ids.iter().map(|hotkey_id| (*hotkey_id, modification)));
+	}
+
+	/// Create a request to change the keys or handlers of the hotkey with the given id. Will be applied on the next update.
+	pub(crate) fn request_rebind(&self, hotkey_id:u64, rebind:Rebind) {
+		self.rebinds.lock().unwrap().push((hotkey_id, rebind));
+	}
+
+	/// Remove the hotkeys with the given ids at once, releasing those that are pressed. Waits for the current update to finish, unless called from the thread executing it, in which case the hotkeys are removed on the next update.
+	pub(crate) fn unregister(&self, hotkey_ids:&[u64]) {
+		self.request_modification(hotkey_ids, ModificationRequest::Unregister);
+		self.with_hotkeys(|_| {});
+	}
+
+	/// Create a snapshot of all hotkeys, in the order they are updated. Returns an empty list if the current thread is already executing an action on the hotkeys.
+	pub(crate) fn snapshot(&self) -> Vec<HotkeyInfo> {
+		self.with_hotkeys(|hotkeys| hotkeys.iter_mut().map(|hotkey| {
+			hotkey.apply_modifications();
+			hotkey.info()
+		}).collect()).unwrap_or_default()
+	}
+
+	/// Update all hotkeys with a changed key and the time it changed. Returns true if any of the hotkeys blocks.
+	pub(crate) fn update(&self, state_change_pattern:KeyPattern, active_pattern:&KeyPattern, timestamp:SystemTime) -> bool {
+		let key_down:bool = state_change_pattern & *active_pattern != KeyPattern::ZERO;
+		let expiring_layers:Vec<String> = if key_down { layers::expiring_one_shot_layers(state_change_pattern) } else { Vec::new() };
+		let blocking:bool = self.with_hotkeys(|hotkeys| {
+
+			// Hotkeys on inactive layers are unavailable.
+			let layer_stack:Vec<String> = layers::active_layers();
+			let ranks:Vec<Option<usize>> = hotkeys.iter().map(|hotkey| layers::layer_rank(hotkey.assigned_layer(), &layer_stack)).collect();
+			let mut available:Vec<bool> = ranks.iter().map(Option::is_some).collect();
+
+			// Predicates of hotkeys are evaluated in the same context.
+			let context:HotkeyContext = HotkeyContext::new(timestamp, *active_pattern, layer_stack);
+
+			// Hotkeys with the same keys as an enabled hotkey on a higher layer are unavailable too. When the changed key is one of the keys, the higher hotkey only takes precedence if its predicate and window scope allow it to be pressed, or it is already pressed.
+			let mut layer_groups:HashMap<KeyPattern, Vec<usize>> = HashMap::new();
+			for (index, hotkey) in hotkeys.iter().enumerate().filter(|(index, _)| available[*index]) {
+				layer_groups.entry(hotkey.key_pattern()).or_default().push(index);
+			}
+			for (key_pattern, group) in layer_groups.iter().filter(|(_, group)| group.len() > 1) {
+				let affected:bool = *key_pattern & state_change_pattern != KeyPattern::ZERO;
+				let top_rank:Option<usize> = group.iter().filter(|index| {
+					let hotkey:&Hotkey = &hotkeys[**index];
+					hotkey.enabled() && (!affected || hotkey.pressed() || hotkey.allowed_in(&context))
+				}).map(|index| ranks[*index]).max().flatten();
+				for index in group {
+					available[*index] = ranks[*index] >= top_rank;
+				}
+			}
+			let mut blocking:bool = false;
+			for (hotkey, available) in hotkeys.iter_mut().zip(available) {
+				if hotkey.update_state(state_change_pattern, active_pattern, available, &context) {
+					blocking = true;
+				}
+			}
+			blocking
+		}).unwrap_or(false);
+
+		// One-shot layers are deactivated after the next key press.
+		if !expiring_layers.is_empty() {
+			layers::expire_one_shot_layers(&expiring_layers);
+		}
+		blocking
+	}
+
+	/// Notify the hotkey with the given id that one of its timers expired.
+	pub(crate) fn timer_expired(&self, hotkey_id:u64, generation:u64) {
+		self.with_hotkeys(|hotkeys| {
+			if let Some(hotkey) = hotkeys.iter_mut().find(|hotkey| hotkey.id() == hotkey_id) {
+				hotkey.timer_expired(generation);
+			}
+		});
+	}
+
+	/// Execute an action on all hotkeys, after applying requested modifications and rebinds. Returns None if the current thread is already executing an action on the hotkeys.
+	fn with_hotkeys<T, U>(&self, action:T) -> Option<U> where T:FnOnce(&mut Vec<Hotkey>) -> U {
+
+		// Key changes caused by the handlers of hotkeys do not update the hotkeys again.
+		if UPDATING.with(|updating| updating.replace(true)) {
+			return None;
+		}
+		let update_lock:MutexGuard<'_, ()> = self.update_lock.lock().unwrap_or_else(PoisonError::into_inner); // A panicking action does not leave the hotkeys in an invalid state.
+
+		// Take the hotkeys out of the registry, so handlers can register new hotkeys while the hotkeys are updating. The guard puts them back, even if the action panics.
+		let mut guard:UpdateGuard<'_> = UpdateGuard { registry: self, hotkeys: mem::take(&mut *self.hotkeys.lock().unwrap()), _update_lock: update_lock };
+		let modifications:Vec<(u64, ModificationRequest)> = mem::take(&mut *self.modifications.lock().unwrap()); // Release handlers of unregistered hotkeys can request modifications too.
+		for (hotkey_id, modification) in modifications {
+			if let ModificationRequest::Unregister = modification {
+				if let Some(index) = guard.hotkeys.iter().position(|hotkey| hotkey.id() == hotkey_id) {
+					guard.hotkeys.remove(index).release(); // Handlers like those of remaps would otherwise leave their keys held.
+				}
+			} else if let Some(hotkey) = guard.hotkeys.iter_mut().find(|hotkey| hotkey.id() == hotkey_id) {
+				hotkey.request_modification(modification);
+			}
+		}
+		let rebinds:Vec<(u64, Rebind)> = mem::take(&mut *self.rebinds.lock().unwrap()); // Release handlers of rebound hotkeys can request rebinds too.
+		for (hotkey_id, rebind) in rebinds {
+			if let Some(hotkey) = guard.hotkeys.iter_mut().find(|hotkey| hotkey.id() == hotkey_id) {
+				hotkey.rebind(rebind);
+			}
+		}
+
+		// Execute the action.
+		Some(action(&mut guard.hotkeys))
+	}
+}
+
+
+
+/// Hotkeys taken out of the registry while executing an action on them. Puts them back and ends the update when dropped.
+struct UpdateGuard<'a> {
+	registry:&'a HotkeyRegistry,
+	hotkeys:Vec<Hotkey>,
+	_update_lock:MutexGuard<'a, ()>
+}
+impl Drop for UpdateGuard<'_> {
+	fn drop(&mut self) {
+
+		// Put the hotkeys back, followed by any hotkeys the handlers registered.
+		let mut registered_hotkeys:MutexGuard<'_, Vec<Hotkey>> = self.registry.hotkeys.lock().unwrap_or_else(PoisonError::into_inner);
+		let mut hotkeys:Vec<Hotkey> = mem::take(&mut self.hotkeys);
+		hotkeys.append(&mut registered_hotkeys);
+		*registered_hotkeys = hotkeys;
+		UPDATING.with(|updating| updating.set(false));
+	}
+}
\ No newline at end of file
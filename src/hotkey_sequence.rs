@@ -0,0 +1,119 @@
+use std::time::{ Duration, Instant };
+use crate::KeyPattern;
+
+
+
+pub(crate) const DEFAULT_STEP_TIMEOUT:Duration = Duration::from_millis(1000);
+
+
+
+pub(crate) enum SequenceState {
+	Progressing { consumed:bool }, // The sequence has not reached its final step yet. Consumed events are part of a completed step.
+	Armed // All steps but the final one are pressed, the final step is handled like a normal hotkey.
+}
+
+
+
+/// Tracks the progress of a hotkey that requires multiple key-patterns to be pressed in order.
+pub(crate) struct HotkeySequence {
+	steps:Vec<KeyPattern>, // All steps except the final one, which is the key-pattern of the hotkey itself.
+	step_timeout:Duration,
+	progress:usize,
+	last_step_time:Instant,
+	awaiting_release:KeyPattern
+}
+impl HotkeySequence {
+
+	/* CONSTRUCTOR METHODS */
+
+	/// Create a new sequence tracker for all steps preceding the final step.
+	pub(crate) fn new(steps:Vec<KeyPattern>) -> HotkeySequence {
+		HotkeySequence {
+			steps,
+			step_timeout: DEFAULT_STEP_TIMEOUT,
+			progress: 0,
+			last_step_time: Instant::now(),
+			awaiting_release: KeyPattern::ZERO
+		}
+	}
+
+
+
+	/* PROPERTY GETTER METHODS */
+
+	/// Get all steps except the final one.
+	pub(crate) fn steps(&self) -> &[KeyPattern] {
+		&self.steps
+	}
+
+
+
+	/* PROPERTY SETTER METHODS */
+
+	/// Set the maximum time between two steps.
+	pub(crate) fn set_step_timeout(&mut self, timeout:Duration) {
+		self.step_timeout = timeout;
+	}
+
+
+
+	/* USAGE METHODS */
+
+	/// Reset the progress to the first step.
+	pub(crate) fn reset(&mut self) {
+		self.progress = 0;
+		self.awaiting_release = KeyPattern::ZERO;
+	}
+
+	/// Update the progress of the sequence with a changed key.
+	pub(crate) fn update(&mut self, state_change_pattern:KeyPattern, active_pattern:&KeyPattern, final_step:KeyPattern, final_step_active:bool) -> SequenceState {
+		let key_down:bool = state_change_pattern & *active_pattern != KeyPattern::ZERO;
+
+		// Repeats and the release of the key that completed the last step belong to that step.
+		if self.awaiting_release & state_change_pattern != KeyPattern::ZERO {
+			if !key_down {
+				self.awaiting_release = KeyPattern::ZERO;
+			}
+			return SequenceState::Progressing { consumed: true };
+		}
+
+		// While the final step is held, let the hotkey handle it.
+		if final_step_active {
+			return SequenceState::Armed;
+		}
+
+		// Start over when the user waited too long between two steps.
+		if self.progress > 0 && self.last_step_time.elapsed() > self.step_timeout {
+			self.reset();
+		}
+
+		// When all steps but the final one are pressed, any unrelated key press breaks the sequence.
+		if self.progress == self.steps.len() {
+			if !key_down || state_change_pattern & final_step != KeyPattern::ZERO {
+				return SequenceState::Armed;
+			}
+			self.reset();
+		}
+		if !key_down {
+			return SequenceState::Progressing { consumed: false };
+		}
+
+		// Pressing a key that is not part of the expected step breaks the sequence, but might start it over.
+		if state_change_pattern & self.steps[self.progress] == KeyPattern::ZERO {
+			self.reset();
+			if state_change_pattern & self.steps[0] == KeyPattern::ZERO {
+				return SequenceState::Progressing { consumed: false };
+			}
+		}
+
+		// Move to the next step when all keys of the current step are held.
+		let step:KeyPattern = self.steps[self.progress];
+		if *active_pattern & step == step {
+			self.progress += 1;
+			self.last_step_time = Instant::now();
+			self.awaiting_release = state_change_pattern;
+			return SequenceState::Progressing { consumed: true };
+		}
+		SequenceState::Progressing { consumed: false }
+	}
+}
\ No newline at end of file
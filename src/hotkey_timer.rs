@@ -0,0 +1,64 @@
+use std::{ sync::{ Condvar, Mutex, MutexGuard, Once }, thread, time::Instant };
+
+
+
+pub(crate) static HOTKEY_TIMER:HotkeyTimer = HotkeyTimer::new();
+type TimerCallback = Box<dyn FnOnce() + Send>;
+
+
+
+/// Executes callbacks when their timers expire, so hotkeys can act without waiting for the next key event. Runs a single thread, which is started when the first timer is scheduled.
+pub(crate) struct HotkeyTimer {
+	timers:Mutex<Vec<(Instant, TimerCallback)>>,
+	timers_changed:Condvar,
+	start:Once
+}
+impl HotkeyTimer {
+
+	/* CONSTRUCTOR METHODS */
+
+	/// Create a new timer without any scheduled timers.
+	pub(crate) const fn new() -> HotkeyTimer {
+		HotkeyTimer {
+			timers: Mutex::new(Vec::new()),
+			timers_changed: Condvar::new(),
+			start: Once::new()
+		}
+	}
+
+
+
+	/* USAGE METHODS */
+
+	/// Schedule a callback to execute once the expiration time is reached.
+	pub(crate) fn schedule<T>(&'static self, expiration:Instant, callback:T) where T:FnOnce() + Send + 'static {
+		self.start.call_once(|| {
+			thread::spawn(|| self.run());
+		});
+		self.timers.lock().unwrap().push((expiration, Box::new(callback)));
+		self.timers_changed.notify_all();
+	}
+
+	/// Wait for timers to expire and execute their callbacks.
+	fn run(&self) {
+		let mut timers:MutexGuard<'_, Vec<(Instant, TimerCallback)>> = self.timers.lock().unwrap();
+		loop {
+			match timers.iter().enumerate().min_by_key(|(_, (expiration, _))| *expiration).map(|(index, (expiration, _))| (index, *expiration)) {
+				None => timers = self.timers_changed.wait(timers).unwrap(),
+				Some((index, expiration)) => {
+					let now:Instant = Instant::now();
+					if expiration > now {
+						timers = self.timers_changed.wait_timeout(timers, expiration - now).unwrap().0;
+						continue;
+					}
+					let (_, callback) = timers.remove(index);
+
+					// Execute the callback without holding the lock, so it can schedule new timers.
+					drop(timers);
+					callback();
+					timers = self.timers.lock().unwrap();
+				}
+			}
+		}
+	}
+}
\ No newline at end of file
@@ -0,0 +1,96 @@
+use crate::{ HotkeyHandle, KeyPattern, MatchMode, hotkey_registry::REGISTERED_HOTKEYS };
+
+
+
+/// A snapshot of a registered hotkey.
+#[derive(Clone, Debug)]
+pub struct HotkeyInfo {
+	/// The handle to modify or unregister the hotkey with.
+	pub handle:HotkeyHandle,
+	/// The keys of the hotkey. For sequences, the keys of the final step.
+	pub keys:KeyPattern,
+	/// The steps preceding the final step of a sequence. Empty for other hotkeys.
+	pub steps:Vec<KeyPattern>,
+	/// The key that has to be pressed last, if any. It is part of the keys.
+	pub trigger:Option<KeyPattern>,
+	/// The layer the hotkey only triggers on, if any.
+	pub layer:Option<String>,
+	/// How other held keys affect whether the keys match.
+	pub match_mode:MatchMode,
+	/// Whether the hotkey blocks its key events from reaching other processes.
+	pub blocking:bool,
+	/// Whether the handlers execute on the thread of the hook, instead of the worker pool.
+	pub inline:bool,
+	/// Whether the hotkey has a predicate or window scope, which may prevent it from triggering.
+	pub conditional:bool,
+	/// Whether the hotkey is enabled.
+	pub enabled:bool,
+	/// Whether the hotkey is currently pressed.
+	pub pressed:bool
+}
+
+
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ConflictKind {
+	/// Both hotkeys have the same keys on the same layer. Both trigger, in the order they were registered in.
+	Duplicate,
+	/// Both hotkeys have the same keys on different layers. While both layers are active, only the hotkey on the higher layer triggers.
+	Shadowed,
+	/// Both hotkeys block, the keys of the first are a subset of the keys of the second and its match mode allows the extra keys. Pressing the second also triggers the first. Layers do not prevent this, as any layers can be active at the same time.
+	BlockingSubset
+}
+
+/// Two registered hotkeys that interfere with each other.
+#[derive(Clone, Debug)]
+pub struct HotkeyConflict {
+	/// How the hotkeys interfere.
+	pub kind:ConflictKind,
+	/// The first hotkey. For blocking subsets, the hotkey with the fewest keys.
+	pub first:HotkeyInfo,
+	/// The second hotkey.
+	pub second:HotkeyInfo
+}
+
+
+
+/// Get a snapshot of all registered hotkeys, in the order they are updated. Requested modifications are applied first. Returns an empty list when called from an inline handler.
+pub fn list_registered() -> Vec<HotkeyInfo> {
+	REGISTERED_HOTKEYS.snapshot()
+}
+
+/// Find all pairs of enabled registered hotkeys that interfere with each other. Conditional hotkeys are included, as their conditions may hold at the same time.
+pub fn conflicts() -> Vec<HotkeyConflict> {
+	let hotkeys:Vec<HotkeyInfo> = list_registered().into_iter().filter(|hotkey| hotkey.enabled).collect();
+	let mut conflicts:Vec<HotkeyConflict> = Vec::new();
+	for (index, first) in hotkeys.iter().enumerate() {
+		for second in &hotkeys[index + 1..] {
+			if let Some(conflict) = conflict(first, second) {
+				conflicts.push(conflict);
+			}
+		}
+	}
+	conflicts
+}
+
+/// Find the conflict between two hotkeys, if any.
+fn conflict(first:&HotkeyInfo, second:&HotkeyInfo) -> Option<HotkeyConflict> {
+	let (kind, first, second) = if first.keys == second.keys {
+
+		// Layer precedence only compares the keys, sequences and trigger keys do not matter.
+		if first.layer != second.layer {
+			(ConflictKind::Shadowed, first, second)
+		} else if first.steps == second.steps && first.trigger == second.trigger {
+			(ConflictKind::Duplicate, first, second)
+		} else {
+			return None;
+		}
+	} else if first.blocking && second.blocking && first.match_mode.matches(first.keys, &second.keys) {
+		(ConflictKind::BlockingSubset, first, second)
+	} else if first.blocking && second.blocking && second.match_mode.matches(second.keys, &first.keys) {
+		(ConflictKind::BlockingSubset, second, first)
+	} else {
+		return None;
+	};
+	Some(HotkeyConflict { kind, first: first.clone(), second: second.clone() })
+}
\ No newline at end of file
@@ -0,0 +1,70 @@
+#[cfg(test)]
+mod tests {
+	use crate::{ Hotkey, HotkeyHandle, MatchMode, hokey_u::tests::HOTKEY_LOCK, hotkeys::{ self, ConflictKind, HotkeyConflict, HotkeyInfo }, keys };
+	use std::sync::MutexGuard;
+
+
+
+	/// Find the snapshot of a registered hotkey.
+	fn info(handle:&HotkeyHandle) -> Option<HotkeyInfo> {
+		hotkeys::list_registered().into_iter().find(|hotkey| &hotkey.handle == handle)
+	}
+
+	/// Find the kinds of all conflicts between the given hotkeys, with the indexes of the hotkeys.
+	fn conflicts_between(handles:&[&HotkeyHandle]) -> Vec<(ConflictKind, usize, usize)> {
+		let index = |handle:&HotkeyHandle| handles.iter().position(|own_handle| *own_handle == handle);
+		hotkeys::conflicts().iter().filter_map(|conflict:&HotkeyConflict| Some((conflict.kind, index(&conflict.first.handle)?, index(&conflict.second.handle)?))).collect()
+	}
+
+
+
+	#[test]
+	fn test_list_registered() {
+		let _lock:MutexGuard<'_, ()> = HOTKEY_LOCK.lock().unwrap();
+		let mut handle:HotkeyHandle = Hotkey::sequence(&[&[keys::VOLUME_MUTE], &[keys::LSHIFT, keys::VOLUME_MUTE]]).match_mode(MatchMode::Exact).layer("list_registered").when(|_| true).blocking().register();
+
+		let hotkey:HotkeyInfo = info(&handle).unwrap();
+		assert_eq!(hotkey.keys, keys::LSHIFT.pattern() | keys::VOLUME_MUTE);
+		assert_eq!(hotkey.steps, vec![keys::VOLUME_MUTE.pattern()]);
+		assert_eq!(hotkey.trigger, None);
+		assert_eq!(hotkey.layer.as_deref(), Some("list_registered"));
+		assert_eq!(hotkey.match_mode, MatchMode::Exact);
+		assert!(hotkey.blocking && !hotkey.inline && hotkey.conditional && hotkey.enabled && !hotkey.pressed);
+
+		handle.disable();
+		assert!(!info(&handle).unwrap().enabled);
+		handle.clone().unregister();
+		assert!(info(&handle).is_none());
+	}
+
+	#[test]
+	fn test_conflicts() {
+		let _lock:MutexGuard<'_, ()> = HOTKEY_LOCK.lock().unwrap();
+		let handles:Vec<HotkeyHandle> = vec![
+			Hotkey::new(&[keys::VOLUME_DOWN]).register(),
+			Hotkey::new(&[keys::VOLUME_DOWN]).register(),
+			Hotkey::new(&[keys::VOLUME_DOWN]).layer("conflicts").register(),
+			Hotkey::new(&[keys::VOLUME_DOWN]).disabled().register(),
+			Hotkey::new(&[keys::VOLUME_UP, keys::F18]).blocking().register(),
+			Hotkey::new(&[keys::VOLUME_UP]).blocking().register(),
+			Hotkey::new(&[keys::F18]).register(),
+			Hotkey::new(&[keys::VOLUME_UP]).trigger(keys::F18).blocking().register(),
+			Hotkey::new(&[keys::F19, keys::F20]).blocking().register(),
+			Hotkey::new(&[keys::F19]).match_mode(MatchMode::Exact).blocking().register(),
+			Hotkey::new(&[keys::F20]).match_mode(MatchMode::IgnoreNonModifiers).blocking().register()
+		];
+
+		let handle_refs:Vec<&HotkeyHandle> = handles.iter().collect();
+		assert_eq!(conflicts_between(&handle_refs), vec![
+			(ConflictKind::Duplicate, 0, 1),
+			(ConflictKind::Shadowed, 0, 2),
+			(ConflictKind::Shadowed, 1, 2),
+			(ConflictKind::BlockingSubset, 5, 4),
+			(ConflictKind::BlockingSubset, 5, 7),
+			(ConflictKind::BlockingSubset, 10, 8)
+		]);
+		for handle in handles {
+			handle.unregister();
+		}
+	}
+}
\ No newline at end of file
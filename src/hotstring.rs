@@ -0,0 +1,168 @@
+use std::sync::{ Mutex, MutexGuard };
+use crate::{ InputBuilder, Key, keys };
+
+
+
+static REGISTERED_HOTSTRINGS:Mutex<Vec<Hotstring>> = Mutex::new(Vec::new());
+static TYPED_CHARACTERS:Mutex<String> = Mutex::new(String::new());
+static BLOCKED_RELEASE:Mutex<Option<u8>> = Mutex::new(None);
+const MAX_TYPED_CHARACTERS:usize = 100;
+const DEFAULT_END_CHARS:&str = " \t\n-()[]{}':;\"/\\,.?!";
+const RESET_KEYS:&[Key] = &[keys::LBUTTON, keys::RBUTTON, keys::MBUTTON, keys::ESCAPE, keys::LEFT, keys::RIGHT, keys::UP, keys::DOWN, keys::HOME, keys::END, keys::PRIOR, keys::NEXT];
+const NON_TYPING_MODIFIERS:&[Key] = &[keys::CONTROL, keys::LCONTROL, keys::RCONTROL, keys::ALT, keys::LALT, keys::RALT, keys::LWIN, keys::RWIN];
+
+
+
+/// Replaces typed text with other text. When the trigger is typed as a separate word, it is erased with backspaces and the replacement is typed instead.
+/// Typed characters are tracked from physical key presses, so hotstrings require the key hook to be installed. Mouse clicks and navigation keys reset the typed characters.
+pub struct Hotstring {
+	trigger:String,
+	replacement:String,
+	end_chars:String,
+	immediate:bool,
+	case_preserving:bool
+}
+impl Hotstring {
+
+	/* CONSTRUCTOR METHODS */
+
+	/// Create a new hotstring that replaces the trigger with the replacement. By default the trigger is matched case-insensitively and only replaced once an end character like a space or punctuation is typed after it.
+	pub fn new(trigger:&str, replacement:&str) -> Hotstring {
+		Hotstring {
+			trigger: trigger.to_string(),
+			replacement: replacement.to_string(),
+			end_chars: DEFAULT_END_CHARS.to_string(),
+			immediate: false,
+			case_preserving: false
+		}
+	}
+
+	/// Return self with different end characters. The trigger is only replaced once one of these characters is typed after it. The end character is typed again after the replacement.
+	pub fn end_chars(mut self, end_chars:&str) -> Self {
+		self.end_chars = end_chars.to_string();
+		self
+	}
+
+	/// Return self replacing the trigger as soon as it is typed, without waiting for an end character. The key completing the trigger is blocked if the hook can block keys.
+	pub fn immediate(mut self) -> Self {
+		self.immediate = true;
+		self
+	}
+
+	/// Return self matching the case of the typed trigger. A trigger typed in all capitals types the replacement in capitals and a trigger starting with a capital types the replacement starting with a capital.
+	pub fn case_preserving(mut self) -> Self {
+		self.case_preserving = true;
+		self
+	}
+
+
+
+	/* REGISTERED STATIC METHODS */
+
+	/// Register the hotstring to the static list.
+	pub fn register(self) {
+		REGISTERED_HOTSTRINGS.lock().unwrap().push(self);
+	}
+
+
+
+	/* USAGE METHODS */
+
+	/// Check if the typed characters end with the trigger. Returns the typed trigger and end character if so.
+	fn find_match<'a>(&self, typed:&'a str) -> Option<(&'a str, Option<char>)> {
+
+		// Split off the end character.
+		let (typed, end_char):(&str, Option<char>) = if self.immediate {
+			(typed, None)
+		} else {
+			let end_char:char = typed.chars().last().filter(|character| self.end_chars.contains(*character))?;
+			(&typed[..typed.len() - end_char.len_utf8()], Some(end_char))
+		};
+
+		// Only match the trigger as a separate word.
+		let trigger_start:usize = typed.len().checked_sub(self.trigger.len())?;
+		let typed_trigger:&str = typed.get(trigger_start..)?;
+		if !typed_trigger.eq_ignore_ascii_case(&self.trigger) || typed[..trigger_start].chars().last().is_some_and(|character| character.is_alphanumeric()) {
+			return None;
+		}
+		Some((typed_trigger, end_char))
+	}
+
+	/// Get the replacement for a typed trigger, matching the case of the typed trigger if the hotstring is case-preserving.
+	fn replacement_for(&self, typed_trigger:&str) -> String {
+		let letters:Vec<char> = typed_trigger.chars().filter(|character| character.is_alphabetic()).collect();
+		if !self.case_preserving || !letters.first().is_some_and(|letter| letter.is_uppercase()) {
+			return self.replacement.clone();
+		}
+		if letters.len() > 1 && letters.iter().all(|letter| letter.is_uppercase()) {
+			return self.replacement.to_uppercase();
+		}
+		let mut characters:std::str::Chars<'_> = self.replacement.chars();
+		match characters.next() {
+			Some(first) => first.to_uppercase().chain(characters).collect(),
+			None => String::new()
+		}
+	}
+}
+
+
+
+/// Handle a physical key event for the typed characters. When a registered hotstring is typed, its trigger is replaced in a separate thread. Returns true if the event should be blocked, which is the case for the key completing an immediate hotstring when the hook can block it.
+pub(crate) fn handle_typed_key(key_code:u8, down:bool) -> bool {
+	if !down {
+		let mut blocked_release:MutexGuard<'_, Option<u8>> = BLOCKED_RELEASE.lock().unwrap();
+		if *blocked_release == Some(key_code) {
+			*blocked_release = None;
+			return true;
+		}
+		return false;
+	}
+	let key:Key = Key::new(key_code);
+	let mut typed:MutexGuard<'_, String> = TYPED_CHARACTERS.lock().unwrap();
+
+	// Keep track of typed characters.
+	if key == keys::BACK {
+		typed.pop();
+		return false;
+	}
+	if RESET_KEYS.contains(&key) {
+		typed.clear();
+		return false;
+	}
+	if NON_TYPING_MODIFIERS.iter().any(|modifier| modifier.down()) {
+		return false;
+	}
+	let shifted:bool = [keys::SHIFT, keys::LSHIFT, keys::RSHIFT].iter().any(|shift| shift.down());
+	let Some(character) = key.to_char(shifted) else {
+		return false;
+	};
+	typed.push(character);
+	if typed.len() > MAX_TYPED_CHARACTERS {
+		typed.remove(0);
+	}
+
+	// Replace the first matching hotstring. When the hook can block it, the last character of an immediate trigger is blocked, so it does not need to be erased.
+	let replacement:Option<(usize, String, bool)> = REGISTERED_HOTSTRINGS.lock().unwrap().iter().find_map(|hotstring| {
+		let blocking:bool = hotstring.immediate && crate::key_hook::can_block();
+		hotstring.find_match(&typed).map(|(typed_trigger, end_char)| (
+			(typed_trigger.chars().count() + end_char.map(|_| 1).unwrap_or(0)).saturating_sub(blocking as usize),
+			hotstring.replacement_for(typed_trigger) + &end_char.map(String::from).unwrap_or_default(),
+			blocking
+		))
+	});
+	let Some((erase_count, replacement, blocking)) = replacement else {
+		return false;
+	};
+	typed.clear();
+	drop(typed);
+	if blocking {
+		*BLOCKED_RELEASE.lock().unwrap() = Some(key_code);
+	}
+	let mut inputs:InputBuilder = InputBuilder::new();
+	for _ in 0..erase_count {
+		inputs.add_click(&keys::BACK);
+	}
+	inputs.add_send_str(&replacement, 0);
+	drop(inputs.execute_async()); // The inputs finish in the background.
+	blocking
+}
\ No newline at end of file
@@ -0,0 +1,122 @@
+#[cfg(test)]
+mod tests {
+	use crate::{ Hotstring, InputBuilder, Key, RecordedAction, RecordingBackend, hokey_u::tests::HOTKEY_LOCK, input_builder_u::tests::{ GLOBAL_BACKEND_LOCK, global_recorder }, key_hook::{ self, simulate }, keys };
+	use std::{ sync::MutexGuard, thread, time::{ Duration, Instant } };
+
+
+
+	/// Simulate typing a string. Starts by resetting the typed characters.
+	fn type_str(text:&str) {
+		simulate(keys::ESCAPE, true);
+		simulate(keys::ESCAPE, false);
+		for character in text.chars() {
+			let keys:Vec<Key> = Key::from_char(character);
+			for key in &keys {
+				simulate(*key, true);
+			}
+			for key in keys.iter().rev() {
+				simulate(*key, false);
+			}
+		}
+	}
+
+	/// Wait for the recorder to receive the actions that erase a number of characters and type the replacement, then compare them.
+	fn assert_replaced(recorder:&RecordingBackend, erase_count:usize, replacement:&str) {
+		let expected:RecordingBackend = RecordingBackend::new();
+		let mut inputs:InputBuilder = InputBuilder::new();
+		for _ in 0..erase_count {
+			inputs.add_click(&keys::BACK);
+		}
+		inputs.with_send_str(replacement, 0).execute_with(&expected);
+		let expected_actions:Vec<RecordedAction> = expected.actions();
+
+		let start:Instant = Instant::now();
+		while recorder.actions().len() < expected_actions.len() && start.elapsed() < Duration::from_secs(2) {
+			thread::sleep(Duration::from_millis(5));
+		}
+		assert_eq!(recorder.actions(), expected_actions);
+	}
+
+
+
+	#[test]
+	fn test_end_char() {
+		let _hotkey_lock:MutexGuard<'_, ()> = HOTKEY_LOCK.lock().unwrap();
+		let _backend_lock:MutexGuard<'_, ()> = GLOBAL_BACKEND_LOCK.lock().unwrap();
+		let recorder:RecordingBackend = global_recorder();
+		Hotstring::new("kfbtv", "by the way").register();
+
+		type_str("kfbtv");
+		assert!(recorder.actions().is_empty());
+		type_str("kfbtv.");
+		assert_replaced(&recorder, 6, "by the way.");
+	}
+
+	#[test]
+	fn test_immediate() {
+		let _hotkey_lock:MutexGuard<'_, ()> = HOTKEY_LOCK.lock().unwrap();
+		let _backend_lock:MutexGuard<'_, ()> = GLOBAL_BACKEND_LOCK.lock().unwrap();
+		let recorder:RecordingBackend = global_recorder();
+		Hotstring::new(";kfsig", "Signature").immediate().register();
+
+		// The key completing the trigger is blocked, so one character less is erased.
+		key_hook::set_can_block(true);
+		type_str(";kfsi");
+		assert!(simulate(keys::G, true));
+		assert!(simulate(keys::G, false));
+		key_hook::set_can_block(cfg!(windows));
+		assert_replaced(&recorder, 5, "Signature");
+	}
+
+	#[test]
+	fn test_immediate_without_blocking() {
+		let _hotkey_lock:MutexGuard<'_, ()> = HOTKEY_LOCK.lock().unwrap();
+		let _backend_lock:MutexGuard<'_, ()> = GLOBAL_BACKEND_LOCK.lock().unwrap();
+		let recorder:RecordingBackend = global_recorder();
+		Hotstring::new(";kfnb", "Unblocked").immediate().register();
+
+		// When the hook cannot block, the key completing the trigger reaches other processes and is erased too.
+		key_hook::set_can_block(false);
+		type_str(";kfn");
+		assert!(!simulate(keys::B, true));
+		assert!(!simulate(keys::B, false));
+		key_hook::set_can_block(cfg!(windows));
+		assert_replaced(&recorder, 5, "Unblocked");
+	}
+
+	#[test]
+	fn test_case_preserving() {
+		let _hotkey_lock:MutexGuard<'_, ()> = HOTKEY_LOCK.lock().unwrap();
+		let _backend_lock:MutexGuard<'_, ()> = GLOBAL_BACKEND_LOCK.lock().unwrap();
+		let recorder:RecordingBackend = global_recorder();
+		Hotstring::new("kfcp", "case preserved").case_preserving().end_chars(" ").register();
+
+		type_str("Kfcp ");
+		assert_replaced(&recorder, 5, "Case preserved ");
+		let recorder:RecordingBackend = global_recorder();
+		type_str("KFCP ");
+		assert_replaced(&recorder, 5, "CASE PRESERVED ");
+		let recorder:RecordingBackend = global_recorder();
+		type_str("kfcp.");
+		assert!(recorder.actions().is_empty());
+	}
+
+	#[test]
+	fn test_word_boundary_and_backspace() {
+		let _hotkey_lock:MutexGuard<'_, ()> = HOTKEY_LOCK.lock().unwrap();
+		let _backend_lock:MutexGuard<'_, ()> = GLOBAL_BACKEND_LOCK.lock().unwrap();
+		let recorder:RecordingBackend = global_recorder();
+		Hotstring::new("kfbz", "boundary").register();
+
+		type_str("xkfbz ");
+		assert!(recorder.actions().is_empty());
+		type_str(" kfbx");
+		simulate(keys::BACK, true);
+		simulate(keys::BACK, false);
+		simulate(keys::Z, true);
+		simulate(keys::Z, false);
+		simulate(keys::SPACE, true);
+		simulate(keys::SPACE, false);
+		assert_replaced(&recorder, 5, "boundary ");
+	}
+}
\ No newline at end of file
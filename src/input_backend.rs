@@ -0,0 +1,165 @@
+use std::{ sync::{ Arc, RwLock, RwLockWriteGuard }, time::Duration };
+use crate::{ Key, keys, sleep };
+
+
+
+static INPUT_BACKEND:RwLock<Option<Arc<dyn InputBackend>>> = RwLock::new(None);
+
+
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum MouseButton { Left, Right, Middle, X1, X2 }
+impl MouseButton {
+
+	/// Try to get the mouse button a key represents.
+	pub fn from_key(key:&Key) -> Option<MouseButton> {
+		match key.key_code() {
+			0x01 => Some(MouseButton::Left),
+			0x02 => Some(MouseButton::Right),
+			0x04 => Some(MouseButton::Middle),
+			0x05 => Some(MouseButton::X1),
+			0x06 => Some(MouseButton::X2),
+			_ => None
+		}
+	}
+
+	/// Get the key representing this mouse button.
+	pub fn key(&self) -> Key {
+		match self {
+			MouseButton::Left => keys::LBUTTON,
+			MouseButton::Right => keys::RBUTTON,
+			MouseButton::Middle => keys::MBUTTON,
+			MouseButton::X1 => keys::XBUTTON1,
+			MouseButton::X2 => keys::XBUTTON2
+		}
+	}
+}
+
+
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum InputAction {
+	KeyDown(u8),
+	KeyUp(u8),
+	ButtonDown(MouseButton),
+	ButtonUp(MouseButton),
+	MouseMove([i32; 2]),
+	MouseDisplace([i32; 2]),
+	MouseWheel(i32)
+}
+impl InputAction {
+
+	/// Create the action that presses or releases a key. Mouse button keys result in mouse button actions.
+	pub fn from_key(key:&Key, down:bool) -> InputAction {
+		match (MouseButton::from_key(key), down) {
+			(Some(button), true) => InputAction::ButtonDown(button),
+			(Some(button), false) => InputAction::ButtonUp(button),
+			(None, true) => InputAction::KeyDown(key.key_code()),
+			(None, false) => InputAction::KeyUp(key.key_code())
+		}
+	}
+
+	/// If the action changes the state of a key, get the key-code and the new state.
+	pub fn key_alteration(&self) -> Option<(u8, bool)> {
+		match self {
+			InputAction::KeyDown(key_code) => Some((*key_code, true)),
+			InputAction::KeyUp(key_code) => Some((*key_code, false)),
+			InputAction::ButtonDown(button) => Some((button.key().key_code(), true)),
+			InputAction::ButtonUp(button) => Some((button.key().key_code(), false)),
+			_ => None
+		}
+	}
+}
+
+
+
+pub trait InputBackend:Send + Sync {
+
+	/// Press a keyboard key.
+	fn key_down(&self, key_code:u8);
+
+	/// Release a keyboard key.
+	fn key_up(&self, key_code:u8);
+
+	/// Press or release a mouse button.
+	fn mouse_button(&self, button:MouseButton, down:bool);
+
+	/// Move the mouse to an absolute position on the screen.
+	fn mouse_move(&self, position:[i32; 2]);
+
+	/// Move the mouse relative to its current position.
+	fn mouse_displace(&self, offset:[i32; 2]);
+
+	/// Scroll the mouse wheel a number of notches. Positive values scroll away from the user.
+	fn mouse_wheel(&self, delta:i32);
+
+	/// Get the current position of the mouse, relative to the screen.
+	fn mouse_position(&self) -> [i32; 2];
+
+	/// Send a group of actions that are scheduled to execute at the same time. Backends that can send multiple inputs at once should override this.
+	fn send_actions(&self, actions:&[InputAction]) {
+		for action in actions {
+			match *action {
+				InputAction::KeyDown(key_code) => self.key_down(key_code),
+				InputAction::KeyUp(key_code) => self.key_up(key_code),
+				InputAction::ButtonDown(button) => self.mouse_button(button, true),
+				InputAction::ButtonUp(button) => self.mouse_button(button, false),
+				InputAction::MouseMove(position) => self.mouse_move(position),
+				InputAction::MouseDisplace(offset) => self.mouse_displace(offset),
+				InputAction::MouseWheel(delta) => self.mouse_wheel(delta)
+			}
+		}
+	}
+
+	/// Wait between two groups of actions.
+	fn delay(&self, duration_millis:u64) {
+		sleep(Duration::from_millis(duration_millis));
+	}
+}
+
+
+
+/// A backend that discards all actions. Used when no native backend is available.
+#[cfg(not(windows))]
+struct NullBackend;
+#[cfg(not(windows))]
+impl InputBackend for NullBackend {
+	fn key_down(&self, _key_code:u8) {}
+	fn key_up(&self, _key_code:u8) {}
+	fn mouse_button(&self, _button:MouseButton, _down:bool) {}
+	fn mouse_move(&self, _position:[i32; 2]) {}
+	fn mouse_displace(&self, _offset:[i32; 2]) {}
+	fn mouse_wheel(&self, _delta:i32) {}
+	fn mouse_position(&self) -> [i32; 2] { [0, 0] }
+}
+
+
+
+/// Set the backend all inputs are sent through.
+pub fn set_input_backend<T>(backend:T) where T:InputBackend + 'static {
+	*INPUT_BACKEND.write().unwrap() = Some(Arc::new(backend));
+}
+
+/// Get the backend all inputs are sent through. Defaults to the native backend of the platform. On Linux, that is the X11 backend when the 'x11' feature is enabled and the display can be connected to. Otherwise all inputs are discarded, which is reported once.
+pub fn input_backend() -> Arc<dyn InputBackend> {
+	if let Some(backend) = INPUT_BACKEND.read().unwrap().as_ref() {
+		return backend.clone();
+	}
+	let mut backend:RwLockWriteGuard<'_, Option<Arc<dyn InputBackend>>> = INPUT_BACKEND.write().unwrap();
+	backend.get_or_insert_with(default_input_backend).clone()
+}
+
+/// Create the native backend of the platform. Reports falling back to discarding all inputs.
+fn default_input_backend() -> Arc<dyn InputBackend> {
+	#[cfg(windows)]
+	return Arc::new(crate::WindowsBackend);
+	#[cfg(all(target_os="linux", feature="x11"))]
+	match crate::X11Backend::new() {
+		Ok(backend) => return Arc::new(backend),
+		Err(error) => eprintln!("Could not create the X11 input backend, inputs are discarded: {error}")
+	}
+	#[cfg(not(any(windows, all(target_os="linux", feature="x11"))))]
+	eprintln!("No native input backend is available, inputs are discarded. Enable the 'x11' feature or set a backend with 'set_input_backend'.");
+	#[cfg(not(windows))]
+	return Arc::new(NullBackend);
+}
\ No newline at end of file
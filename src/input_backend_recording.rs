@@ -0,0 +1,128 @@
+use std::sync::{ Arc, Mutex, MutexGuard };
+use crate::{ InputAction, InputBackend, MouseButton };
+
+
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct RecordedAction {
+	pub time_millis:u64,
+	pub action:InputAction
+}
+impl RecordedAction {
+
+	/// Create a new recorded action.
+	pub fn new(time_millis:u64, action:InputAction) -> RecordedAction {
+		RecordedAction { time_millis, action }
+	}
+}
+
+
+
+#[derive(Default)]
+struct RecordingState {
+	time_millis:u64,
+	mouse_position:[i32; 2],
+	actions:Vec<RecordedAction>
+}
+
+
+
+/// A backend that does not send any inputs to the OS, but records all of them. Delays do not sleep, but advance a virtual clock, so every action is recorded with the time it was scheduled at.
+/// Clones share the same recording, which allows keeping a clone to inspect while the original is used as the active backend.
+#[derive(Clone, Default)]
+pub struct RecordingBackend {
+	state:Arc<Mutex<RecordingState>>
+}
+impl RecordingBackend {
+
+	/* CONSTRUCTOR METHODS */
+
+	/// Create a new, empty recording backend.
+	pub fn new() -> RecordingBackend {
+		RecordingBackend::default()
+	}
+
+
+
+	/* PROPERTY GETTER METHODS */
+
+	/// Get a list of all recorded actions.
+	pub fn actions(&self) -> Vec<RecordedAction> {
+		self.state().actions.clone()
+	}
+
+	/// Get the current time of the virtual clock in milliseconds.
+	pub fn time_millis(&self) -> u64 {
+		self.state().time_millis
+	}
+
+
+
+	/* USAGE METHODS */
+
+	/// Take all recorded actions, leaving the recording empty. Does not reset the virtual clock.
+	pub fn take_actions(&self) -> Vec<RecordedAction> {
+		self.state().actions.drain(..).collect()
+	}
+
+	/// Remove all recorded actions and reset the virtual clock.
+	pub fn reset(&self) {
+		let mut state:MutexGuard<'_, RecordingState> = self.state();
+		state.actions.clear();
+		state.time_millis = 0;
+	}
+
+	/// Set the position the mouse is at, as if the user moved it.
+	pub fn set_mouse_position(&self, position:[i32; 2]) {
+		self.state().mouse_position = position;
+	}
+
+	/// Lock the shared state.
+	fn state(&self) -> MutexGuard<'_, RecordingState> {
+		self.state.lock().unwrap()
+	}
+
+	/// Record an action at the current time.
+	fn record(&self, action:InputAction) {
+		let mut state:MutexGuard<'_, RecordingState> = self.state();
+		let time_millis:u64 = state.time_millis;
+		state.actions.push(RecordedAction::new(time_millis, action));
+	}
+}
+impl InputBackend for RecordingBackend {
+	fn key_down(&self, key_code:u8) {
+		self.record(InputAction::KeyDown(key_code));
+	}
+
+	fn key_up(&self, key_code:u8) {
+		self.record(InputAction::KeyUp(key_code));
+	}
+
+	fn mouse_button(&self, button:MouseButton, down:bool) {
+		self.record(if down { InputAction::ButtonDown(button) } else { InputAction::ButtonUp(button) });
+	}
+
+	fn mouse_move(&self, position:[i32; 2]) {
+		self.state().mouse_position = position;
+		self.record(InputAction::MouseMove(position));
+	}
+
+	fn mouse_displace(&self, offset:[i32; 2]) {
+		let mut state:MutexGuard<'_, RecordingState> = self.state();
+		state.mouse_position = [state.mouse_position[0] + offset[0], state.mouse_position[1] + offset[1]];
+		drop(state);
+		self.record(InputAction::MouseDisplace(offset));
+	}
+
+	fn mouse_wheel(&self, delta:i32) {
+		self.record(InputAction::MouseWheel(delta));
+	}
+
+	fn mouse_position(&self) -> [i32; 2] {
+		self.state().mouse_position
+	}
+
+	fn delay(&self, duration_millis:u64) {
+		self.state().time_millis += duration_millis;
+	}
+}
\ No newline at end of file
@@ -0,0 +1,178 @@
+use std::{ error::Error, fs::{ File, OpenOptions }, io::{ self, Write }, mem, os::{ fd::AsRawFd, unix::fs::OpenOptionsExt }, slice, sync::{ Mutex, MutexGuard } };
+use crate::{ InputBackend, MouseButton, evdev_codes::* };
+
+
+
+const UINPUT_PATH:&str = "/dev/uinput";
+const BUS_VIRTUAL:u16 = 0x06;
+
+// Request codes from 'linux/uinput.h'.
+const UI_DEV_CREATE:libc::Ioctl = 0x5501;
+const UI_DEV_DESTROY:libc::Ioctl = 0x5502;
+const UI_SET_EVBIT:libc::Ioctl = 0x40045564;
+const UI_SET_KEYBIT:libc::Ioctl = 0x40045565;
+const UI_SET_RELBIT:libc::Ioctl = 0x40045566;
+const UI_SET_ABSBIT:libc::Ioctl = 0x40045567;
+
+
+
+/// The input backend using virtual devices created through uinput. Works on X11, Wayland and the console alike.
+/// Creates a keyboard with a relative mouse for key, button, wheel and displacement inputs, and an absolute pointer for moving the mouse to a position.
+/// As uinput cannot read the cursor position, the mouse position is tracked from the inputs sent through this backend.
+pub struct UinputBackend {
+	relative_device:Mutex<File>,
+	absolute_device:Mutex<File>,
+	mouse_position:Mutex<[i32; 2]>
+}
+impl UinputBackend {
+
+	/* CONSTRUCTOR METHODS */
+
+	/// Create the virtual devices. The screen size is used as the range of the absolute pointer, so positions map to pixels on a single screen.
+	pub fn new(screen_size:[i32; 2]) -> Result<UinputBackend, Box<dyn Error>> {
+		UinputBackend::with_path(UINPUT_PATH, screen_size)
+	}
+
+	/// Create the virtual devices through a uinput device at a specific path.
+	pub fn with_path(uinput_path:&str, screen_size:[i32; 2]) -> Result<UinputBackend, Box<dyn Error>> {
+
+		// Create a keyboard with a relative mouse.
+		let relative_device:File = UinputBackend::create_device(uinput_path, &format!("{VIRTUAL_DEVICE_NAME_PREFIX} keyboard"), |file| {
+			UinputBackend::ioctl(file, UI_SET_EVBIT, EV_KEY)?;
+			for evdev_code in evdev_key_codes() {
+				UinputBackend::ioctl(file, UI_SET_KEYBIT, evdev_code)?;
+			}
+			UinputBackend::ioctl(file, UI_SET_EVBIT, EV_REL)?;
+			for axis in [REL_X, REL_Y, REL_WHEEL] {
+				UinputBackend::ioctl(file, UI_SET_RELBIT, axis)?;
+			}
+			Ok([0, 0])
+		})?;
+
+		// Create an absolute pointer. The left button is required for it to be recognized as a pointer.
+		let absolute_device:File = UinputBackend::create_device(uinput_path, &format!("{VIRTUAL_DEVICE_NAME_PREFIX} pointer"), |file| {
+			UinputBackend::ioctl(file, UI_SET_EVBIT, EV_KEY)?;
+			UinputBackend::ioctl(file, UI_SET_KEYBIT, BTN_LEFT)?;
+			UinputBackend::ioctl(file, UI_SET_EVBIT, EV_ABS)?;
+			for axis in [ABS_X, ABS_Y] {
+				UinputBackend::ioctl(file, UI_SET_ABSBIT, axis)?;
+			}
+			Ok([screen_size[0] - 1, screen_size[1] - 1])
+		})?;
+
+		Ok(UinputBackend {
+			relative_device: Mutex::new(relative_device),
+			absolute_device: Mutex::new(absolute_device),
+			mouse_position: Mutex::new([0, 0])
+		})
+	}
+
+	/// Create a virtual device. The setup function enables the capabilities of the device and returns the maximum values of the absolute axes.
+	fn create_device<T>(uinput_path:&str, name:&str, setup:T) -> Result<File, Box<dyn Error>> where T:Fn(&File) -> Result<[i32; 2], Box<dyn Error>> {
+		let file:File = OpenOptions::new().write(true).custom_flags(libc::O_NONBLOCK).open(uinput_path)?;
+		let absolute_max:[i32; 2] = setup(&file)?;
+
+		// Write device description.
+		let mut description:libc::uinput_user_dev = unsafe { mem::zeroed() };
+		for (target, source) in description.name.iter_mut().zip(name.bytes().take(libc::UINPUT_MAX_NAME_SIZE - 1)) {
+			*target = source as libc::c_char;
+		}
+		description.id = libc::input_id { bustype: BUS_VIRTUAL, vendor: 0x4B46, product: 0x0001, version: 1 };
+		description.absmax[ABS_X as usize] = absolute_max[0];
+		description.absmax[ABS_Y as usize] = absolute_max[1];
+		(&file).write_all(unsafe { slice::from_raw_parts(&description as *const _ as *const u8, mem::size_of::<libc::uinput_user_dev>()) })?;
+
+		// Create the device.
+		UinputBackend::ioctl(&file, UI_DEV_CREATE, 0)?;
+		Ok(file)
+	}
+
+	/// Execute an ioctl request on a uinput file.
+	fn ioctl(file:&File, request:libc::Ioctl, value:u16) -> Result<(), Box<dyn Error>> {
+		if unsafe { libc::ioctl(file.as_raw_fd(), request, value as libc::c_int) } < 0 {
+			return Err(format!("uinput request {request:#X} failed: {}", std::io::Error::last_os_error()).into());
+		}
+		Ok(())
+	}
+
+
+
+	/* USAGE METHODS */
+
+	/// Write events to a device, followed by a synchronization event. Stops at the first failed write, so no further events are written to a broken device.
+	pub(crate) fn write_events(device:&Mutex<File>, events:&[(u16, u16, i32)]) -> io::Result<()> {
+		let mut device:MutexGuard<'_, File> = device.lock().unwrap();
+		for (event_type, code, value) in events.iter().chain([(EV_SYN, SYN_REPORT, 0)].iter()) {
+			let mut event:libc::input_event = unsafe { mem::zeroed() };
+			event.type_ = *event_type;
+			event.code = *code;
+			event.value = *value;
+			device.write_all(unsafe { slice::from_raw_parts(&event as *const _ as *const u8, mem::size_of::<libc::input_event>()) })?;
+		}
+		Ok(())
+	}
+
+	/// Write events to a device. Input backends cannot return errors, so a failed write is printed.
+	fn send_events(device:&Mutex<File>, events:&[(u16, u16, i32)]) {
+		if let Err(error) = UinputBackend::write_events(device, events) {
+			eprintln!("Could not write to the uinput device, the remaining events were dropped: {error}");
+		}
+	}
+
+	/// Get the evdev key-code of a mouse button.
+	fn button_code(button:MouseButton) -> u16 {
+		match button {
+			MouseButton::Left => BTN_LEFT,
+			MouseButton::Right => BTN_RIGHT,
+			MouseButton::Middle => BTN_MIDDLE,
+			MouseButton::X1 => BTN_SIDE,
+			MouseButton::X2 => BTN_EXTRA
+		}
+	}
+
+	/// Press or release a key by its virtual key-code.
+	fn key(&self, key_code:u8, down:bool) {
+		if let Some(evdev_code) = key_code_to_evdev(key_code) {
+			UinputBackend::send_events(&self.relative_device, &[(EV_KEY, evdev_code, down as i32)]);
+		}
+	}
+}
+impl InputBackend for UinputBackend {
+	fn key_down(&self, key_code:u8) {
+		self.key(key_code, true);
+	}
+
+	fn key_up(&self, key_code:u8) {
+		self.key(key_code, false);
+	}
+
+	fn mouse_button(&self, button:MouseButton, down:bool) {
+		UinputBackend::send_events(&self.relative_device, &[(EV_KEY, UinputBackend::button_code(button), down as i32)]);
+	}
+
+	fn mouse_move(&self, position:[i32; 2]) {
+		*self.mouse_position.lock().unwrap() = position;
+		UinputBackend::send_events(&self.absolute_device, &[(EV_ABS, ABS_X, position[0]), (EV_ABS, ABS_Y, position[1])]);
+	}
+
+	fn mouse_displace(&self, offset:[i32; 2]) {
+		let mut mouse_position:MutexGuard<'_, [i32; 2]> = self.mouse_position.lock().unwrap();
+		*mouse_position = [mouse_position[0] + offset[0], mouse_position[1] + offset[1]];
+		UinputBackend::send_events(&self.relative_device, &[(EV_REL, REL_X, offset[0]), (EV_REL, REL_Y, offset[1])]);
+	}
+
+	fn mouse_wheel(&self, delta:i32) {
+		UinputBackend::send_events(&self.relative_device, &[(EV_REL, REL_WHEEL, delta)]);
+	}
+
+	fn mouse_position(&self) -> [i32; 2] {
+		*self.mouse_position.lock().unwrap()
+	}
+}
+impl Drop for UinputBackend {
+	fn drop(&mut self) {
+		for device in [&self.relative_device, &self.absolute_device] {
+			let _ = UinputBackend::ioctl(&device.lock().unwrap(), UI_DEV_DESTROY, 0);
+		}
+	}
+}
\ No newline at end of file
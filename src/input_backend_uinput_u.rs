@@ -0,0 +1,36 @@
+#[cfg(all(test, target_os="linux", feature="uinput"))]
+mod tests {
+	use crate::{ InputBackend, InputBuilder, UinputBackend, evdev_codes::{ EV_KEY, key_code_to_evdev }, keys };
+	use std::{ fs::{ self, File }, sync::Mutex };
+
+
+
+	#[test]
+	fn test_key_code_to_evdev() {
+		assert_eq!(key_code_to_evdev(keys::A.key_code()), Some(30));
+		assert_eq!(key_code_to_evdev(keys::KEY_0.key_code()), Some(11));
+		assert_eq!(key_code_to_evdev(keys::KEY_1.key_code()), Some(2));
+		assert_eq!(key_code_to_evdev(keys::F12.key_code()), Some(88));
+		assert_eq!(key_code_to_evdev(keys::SHIFT.key_code()), key_code_to_evdev(keys::LSHIFT.key_code()));
+		assert_eq!(key_code_to_evdev(keys::LBUTTON.key_code()), Some(0x110));
+		assert_eq!(key_code_to_evdev(keys::CANCEL.key_code()), None);
+	}
+
+	#[test]
+	fn test_write_error() {
+		let read_only_device:Mutex<File> = Mutex::new(File::open("/dev/null").unwrap());
+		assert!(UinputBackend::write_events(&read_only_device, &[(EV_KEY, 30, 1), (EV_KEY, 30, 0)]).is_err());
+	}
+
+	#[test]
+	#[ignore = "requires write access to /dev/uinput"]
+	fn test_create_devices() {
+		let backend:UinputBackend = UinputBackend::new([1920, 1080]).unwrap();
+		let devices:String = fs::read_to_string("/proc/bus/input/devices").unwrap();
+		assert!(devices.contains("key_flow virtual keyboard"));
+		assert!(devices.contains("key_flow virtual pointer"));
+
+		InputBuilder::new().with_send(&keys::SHIFT, 5).with_mouse_move([100, 100]).with_mouse_displacement([10, -10]).with_mouse_wheel(1).execute_with(&backend);
+		assert_eq!(backend.mouse_position(), [110, 90]);
+	}
+}
\ No newline at end of file
@@ -0,0 +1,110 @@
+use cachew::cache;
+use winapi::{ shared::windef::POINT, um::winuser::{ GetCursorPos, GetSystemMetrics, INPUT, INPUT_KEYBOARD, INPUT_MOUSE, KEYBDINPUT, KEYEVENTF_KEYUP, MOUSEEVENTF_ABSOLUTE, MOUSEEVENTF_LEFTDOWN, MOUSEEVENTF_LEFTUP, MOUSEEVENTF_MIDDLEDOWN, MOUSEEVENTF_MIDDLEUP, MOUSEEVENTF_MOVE, MOUSEEVENTF_RIGHTDOWN, MOUSEEVENTF_RIGHTUP, MOUSEEVENTF_VIRTUALDESK, MOUSEEVENTF_WHEEL, MOUSEEVENTF_XDOWN, MOUSEEVENTF_XUP, MOUSEINPUT, MapVirtualKeyW, SM_CXSCREEN, SM_CYSCREEN, SendInput, WHEEL_DELTA, XBUTTON1, XBUTTON2 } };
+use crate::{ InputAction, InputBackend, MouseButton };
+use std::{ mem, ptr };
+
+
+
+/// The input backend using the Windows API. Sends all actions that are scheduled at the same time in a single 'SendInput' call.
+pub struct WindowsBackend;
+impl WindowsBackend {
+
+	/// Execute the given inputs.
+	fn execute_inputs(mut inputs:Vec<INPUT>) {
+		unsafe { SendInput(inputs.len() as u32, inputs.as_mut_ptr(), mem::size_of::<INPUT>() as i32) };
+	}
+
+	/// Convert an action to a raw input.
+	fn raw_input(action:&InputAction) -> INPUT {
+		const POSITION_MULTIPLIER:i32 = 65535;
+
+		match *action {
+			InputAction::KeyDown(key_code) => Self::raw_key_input(key_code, true),
+			InputAction::KeyUp(key_code) => Self::raw_key_input(key_code, false),
+			InputAction::ButtonDown(button) => Self::raw_button_input(button, true),
+			InputAction::ButtonUp(button) => Self::raw_button_input(button, false),
+			InputAction::MouseMove(position) => {
+				let screen_size:&[i32; 2] = cache!([i32; 2], [GetSystemMetrics(SM_CXSCREEN), GetSystemMetrics(SM_CYSCREEN)]);
+				let normalized_position:[i32; 2] = [position[0] * POSITION_MULTIPLIER / screen_size[0], position[1] * POSITION_MULTIPLIER / screen_size[1]];
+				Self::raw_mouse_input(MOUSEEVENTF_MOVE | MOUSEEVENTF_ABSOLUTE | MOUSEEVENTF_VIRTUALDESK, normalized_position[0], normalized_position[1], 0)
+			},
+			InputAction::MouseDisplace(offset) => Self::raw_mouse_input(MOUSEEVENTF_MOVE, offset[0], offset[1], 0),
+			InputAction::MouseWheel(delta) => Self::raw_mouse_input(MOUSEEVENTF_WHEEL, 0, 0, (delta * WHEEL_DELTA as i32) as u32)
+		}
+	}
+
+	/// Create a raw keyboard input.
+	#[allow(invalid_value)]
+	fn raw_key_input(key_code:u8, down:bool) -> INPUT {
+		unsafe {
+			let mut input_record:INPUT = INPUT { type_: INPUT_KEYBOARD, u: mem::MaybeUninit::uninit().assume_init() };
+			let flags:u32 = if down { 0 } else { KEYEVENTF_KEYUP };
+			let input:KEYBDINPUT = KEYBDINPUT { wVk: key_code as u16, wScan: MapVirtualKeyW(key_code as u32, 0) as u16, dwFlags: flags, time: 0, dwExtraInfo: 0 };
+			ptr::write(&mut input_record.u as *mut _ as *mut KEYBDINPUT, input);
+			input_record
+		}
+	}
+
+	/// Create a raw mouse button input.
+	fn raw_button_input(button:MouseButton, down:bool) -> INPUT {
+		let (flags, mouse_data):(u32, u32) = match (button, down) {
+			(MouseButton::Left, true) => (MOUSEEVENTF_LEFTDOWN, 0),
+			(MouseButton::Left, false) => (MOUSEEVENTF_LEFTUP, 0),
+			(MouseButton::Right, true) => (MOUSEEVENTF_RIGHTDOWN, 0),
+			(MouseButton::Right, false) => (MOUSEEVENTF_RIGHTUP, 0),
+			(MouseButton::Middle, true) => (MOUSEEVENTF_MIDDLEDOWN, 0),
+			(MouseButton::Middle, false) => (MOUSEEVENTF_MIDDLEUP, 0),
+			(MouseButton::X1, true) => (MOUSEEVENTF_XDOWN, XBUTTON1 as u32),
+			(MouseButton::X1, false) => (MOUSEEVENTF_XUP, XBUTTON1 as u32),
+			(MouseButton::X2, true) => (MOUSEEVENTF_XDOWN, XBUTTON2 as u32),
+			(MouseButton::X2, false) => (MOUSEEVENTF_XUP, XBUTTON2 as u32)
+		};
+		Self::raw_mouse_input(flags, 0, 0, mouse_data)
+	}
+
+	/// Create a raw mouse input.
+	#[allow(invalid_value)]
+	fn raw_mouse_input(flags:u32, x:i32, y:i32, mouse_data:u32) -> INPUT {
+		unsafe {
+			let mut input_record:INPUT = INPUT { type_: INPUT_MOUSE, u: mem::MaybeUninit::uninit().assume_init() };
+			let input:MOUSEINPUT = MOUSEINPUT { dx: x, dy: y, mouseData: mouse_data, dwFlags: flags, time: 0, dwExtraInfo: 0 };
+			ptr::write(&mut input_record.u as *mut _ as *mut MOUSEINPUT, input);
+			input_record
+		}
+	}
+}
+impl InputBackend for WindowsBackend {
+	fn key_down(&self, key_code:u8) {
+		Self::execute_inputs(vec![Self::raw_key_input(key_code, true)]);
+	}
+
+	fn key_up(&self, key_code:u8) {
+		Self::execute_inputs(vec![Self::raw_key_input(key_code, false)]);
+	}
+
+	fn mouse_button(&self, button:MouseButton, down:bool) {
+		Self::execute_inputs(vec![Self::raw_button_input(button, down)]);
+	}
+
+	fn mouse_move(&self, position:[i32; 2]) {
+		Self::execute_inputs(vec![Self::raw_input(&InputAction::MouseMove(position))]);
+	}
+
+	fn mouse_displace(&self, offset:[i32; 2]) {
+		Self::execute_inputs(vec![Self::raw_input(&InputAction::MouseDisplace(offset))]);
+	}
+
+	fn mouse_wheel(&self, delta:i32) {
+		Self::execute_inputs(vec![Self::raw_input(&InputAction::MouseWheel(delta))]);
+	}
+
+	fn mouse_position(&self) -> [i32; 2] {
+		let mut cursor_pos:POINT = POINT { x: 0, y: 0 };
+		unsafe { GetCursorPos(&mut cursor_pos); }
+		[cursor_pos.x, cursor_pos.y]
+	}
+
+	fn send_actions(&self, actions:&[InputAction]) {
+		Self::execute_inputs(actions.iter().map(Self::raw_input).collect());
+	}
+}
\ No newline at end of file
@@ -0,0 +1,229 @@
+use x11_dl::{ xlib::{ Display, Xlib }, xtest::Xf86vmode };
+use std::{ error::Error, ffi::CString, os::raw::{ c_int, c_uint, c_ulong }, ptr, sync::{ Mutex, MutexGuard } };
+use crate::{ InputAction, InputBackend, MouseButton };
+
+
+
+// The display connection is only ever used while the mutex is locked, so it can be shared between threads.
+struct DisplayConnection(*mut Display);
+unsafe impl Send for DisplayConnection {}
+
+
+
+/// The input backend using the XTest extension of an X11 server. Xlib and XTest are loaded at runtime, so the libraries are only required when the backend is created.
+pub struct X11Backend {
+	xlib:Xlib,
+	xtest:Xf86vmode,
+	display:Mutex<DisplayConnection>
+}
+impl X11Backend {
+
+	/* CONSTRUCTOR METHODS */
+
+	/// Connect to the X11 server in the 'DISPLAY' environment variable.
+	pub fn new() -> Result<X11Backend, Box<dyn Error>> {
+		X11Backend::connect(None)
+	}
+
+	/// Connect to a specific X11 server, like ":99".
+	pub fn with_display(display_name:&str) -> Result<X11Backend, Box<dyn Error>> {
+		X11Backend::connect(Some(display_name))
+	}
+
+	/// Connect to the given or default X11 server.
+	fn connect(display_name:Option<&str>) -> Result<X11Backend, Box<dyn Error>> {
+		let xlib:Xlib = Xlib::open()?;
+		let xtest:Xf86vmode = Xf86vmode::open()?;
+		let display_name:Option<CString> = display_name.map(CString::new).transpose()?;
+		let display:*mut Display = unsafe { (xlib.XOpenDisplay)(display_name.as_ref().map(|name| name.as_ptr()).unwrap_or(ptr::null())) };
+		if display.is_null() {
+			return Err("Could not connect to X11 display.".into());
+		}
+		Ok(X11Backend { xlib, xtest, display: Mutex::new(DisplayConnection(display)) })
+	}
+
+
+
+	/* USAGE METHODS */
+
+	/// Execute actions on the display and flush them to the server.
+	fn execute_actions(&self, actions:&[InputAction]) {
+		let display:MutexGuard<'_, DisplayConnection> = self.display.lock().unwrap();
+		for action in actions {
+			unsafe { self.execute_action(display.0, action); }
+		}
+		unsafe { (self.xlib.XFlush)(display.0); }
+	}
+
+	/// Execute a single action on the display.
+	unsafe fn execute_action(&self, display:*mut Display, action:&InputAction) {
+		unsafe {
+			match *action {
+				InputAction::KeyDown(key_code) | InputAction::KeyUp(key_code) => {
+					if let Some(keysym) = key_code_to_keysym(key_code) {
+						let keycode:c_uint = (self.xlib.XKeysymToKeycode)(display, keysym) as c_uint;
+						if keycode != 0 {
+							(self.xtest.XTestFakeKeyEvent)(display, keycode, matches!(action, InputAction::KeyDown(_)) as c_int, 0);
+						}
+					}
+				},
+				InputAction::ButtonDown(button) => { (self.xtest.XTestFakeButtonEvent)(display, button_to_x11_button(button), 1, 0); },
+				InputAction::ButtonUp(button) => { (self.xtest.XTestFakeButtonEvent)(display, button_to_x11_button(button), 0, 0); },
+				InputAction::MouseMove(position) => { (self.xtest.XTestFakeMotionEvent)(display, -1, position[0], position[1], 0); },
+				InputAction::MouseDisplace(offset) => {
+					let position:[i32; 2] = self.query_pointer(display);
+					(self.xtest.XTestFakeMotionEvent)(display, -1, position[0] + offset[0], position[1] + offset[1], 0);
+				},
+				InputAction::MouseWheel(delta) => {
+					let button:c_uint = if delta > 0 { 4 } else { 5 };
+					for _ in 0..delta.abs() {
+						(self.xtest.XTestFakeButtonEvent)(display, button, 1, 0);
+						(self.xtest.XTestFakeButtonEvent)(display, button, 0, 0);
+					}
+				}
+			}
+		}
+	}
+
+	/// Get the position of the pointer relative to the root window.
+	unsafe fn query_pointer(&self, display:*mut Display) -> [i32; 2] {
+		let mut root:c_ulong = 0;
+		let mut child:c_ulong = 0;
+		let mut root_position:[c_int; 2] = [0, 0];
+		let mut window_position:[c_int; 2] = [0, 0];
+		let mut mask:c_uint = 0;
+		unsafe {
+			(self.xlib.XQueryPointer)(display, (self.xlib.XDefaultRootWindow)(display), &mut root, &mut child, &mut root_position[0], &mut root_position[1], &mut window_position[0], &mut window_position[1], &mut mask);
+		}
+		root_position
+	}
+}
+impl InputBackend for X11Backend {
+	fn key_down(&self, key_code:u8) {
+		self.execute_actions(&[InputAction::KeyDown(key_code)]);
+	}
+
+	fn key_up(&self, key_code:u8) {
+		self.execute_actions(&[InputAction::KeyUp(key_code)]);
+	}
+
+	fn mouse_button(&self, button:MouseButton, down:bool) {
+		self.execute_actions(&[if down { InputAction::ButtonDown(button) } else { InputAction::ButtonUp(button) }]);
+	}
+
+	fn mouse_move(&self, position:[i32; 2]) {
+		self.execute_actions(&[InputAction::MouseMove(position)]);
+	}
+
+	fn mouse_displace(&self, offset:[i32; 2]) {
+		self.execute_actions(&[InputAction::MouseDisplace(offset)]);
+	}
+
+	fn mouse_wheel(&self, delta:i32) {
+		self.execute_actions(&[InputAction::MouseWheel(delta)]);
+	}
+
+	fn mouse_position(&self) -> [i32; 2] {
+		let display:MutexGuard<'_, DisplayConnection> = self.display.lock().unwrap();
+		unsafe { self.query_pointer(display.0) }
+	}
+
+	fn send_actions(&self, actions:&[InputAction]) {
+		self.execute_actions(actions);
+	}
+}
+impl Drop for X11Backend {
+	fn drop(&mut self) {
+		let display:MutexGuard<'_, DisplayConnection> = self.display.lock().unwrap();
+		unsafe { (self.xlib.XCloseDisplay)(display.0); }
+	}
+}
+
+
+
+/// Get the X11 pointer button number of a mouse button.
+fn button_to_x11_button(button:MouseButton) -> c_uint {
+	match button {
+		MouseButton::Left => 1,
+		MouseButton::Middle => 2,
+		MouseButton::Right => 3,
+		MouseButton::X1 => 8,
+		MouseButton::X2 => 9
+	}
+}
+
+/// Get the X11 keysym for a virtual key-code.
+pub(crate) fn key_code_to_keysym(key_code:u8) -> Option<c_ulong> {
+	let keysym:c_ulong = match key_code {
+		0x08 => 0xFF08, // BackSpace
+		0x09 => 0xFF09, // Tab
+		0x0C => 0xFF0B, // Clear
+		0x0D => 0xFF0D, // Return
+		0x10 | 0xA0 => 0xFFE1, // Shift_L
+		0x11 | 0xA2 => 0xFFE3, // Control_L
+		0x12 | 0xA4 => 0xFFE9, // Alt_L
+		0x13 => 0xFF13, // Pause
+		0x14 => 0xFFE5, // Caps_Lock
+		0x1B => 0xFF1B, // Escape
+		0x20 => 0x0020, // space
+		0x21 => 0xFF55, // Prior
+		0x22 => 0xFF56, // Next
+		0x23 => 0xFF57, // End
+		0x24 => 0xFF50, // Home
+		0x25 => 0xFF51, // Left
+		0x26 => 0xFF52, // Up
+		0x27 => 0xFF53, // Right
+		0x28 => 0xFF54, // Down
+		0x29 => 0xFF60, // Select
+		0x2A | 0x2C => 0xFF61, // Print
+		0x2B => 0xFF62, // Execute
+		0x2D => 0xFF63, // Insert
+		0x2E => 0xFFFF, // Delete
+		0x2F => 0xFF6A, // Help
+		0x30..=0x39 => key_code as c_ulong, // 0-9
+		0x41..=0x5A => key_code as c_ulong + 0x20, // a-z
+		0x5B => 0xFFEB, // Super_L
+		0x5C => 0xFFEC, // Super_R
+		0x5D => 0xFF67, // Menu
+		0x60..=0x69 => 0xFFB0 + (key_code - 0x60) as c_ulong, // KP_0-KP_9
+		0x6A => 0xFFAA, // KP_Multiply
+		0x6B => 0xFFAB, // KP_Add
+		0x6C => 0xFFAC, // KP_Separator
+		0x6D => 0xFFAD, // KP_Subtract
+		0x6E => 0xFFAE, // KP_Decimal
+		0x6F => 0xFFAF, // KP_Divide
+		0x70..=0x87 => 0xFFBE + (key_code - 0x70) as c_ulong, // F1-F24
+		0x90 => 0xFF7F, // Num_Lock
+		0x91 => 0xFF14, // Scroll_Lock
+		0xA1 => 0xFFE2, // Shift_R
+		0xA3 => 0xFFE4, // Control_R
+		0xA5 => 0xFFEA, // Alt_R
+		0xA6 => 0x1008FF26, // XF86Back
+		0xA7 => 0x1008FF27, // XF86Forward
+		0xA8 => 0x1008FF29, // XF86Refresh
+		0xA9 => 0x1008FF28, // XF86Stop
+		0xAA => 0x1008FF1B, // XF86Search
+		0xAB => 0x1008FF30, // XF86Favorites
+		0xAC => 0x1008FF18, // XF86HomePage
+		0xAD => 0x1008FF12, // XF86AudioMute
+		0xAE => 0x1008FF11, // XF86AudioLowerVolume
+		0xAF => 0x1008FF13, // XF86AudioRaiseVolume
+		0xB0 => 0x1008FF17, // XF86AudioNext
+		0xB1 => 0x1008FF16, // XF86AudioPrev
+		0xB2 => 0x1008FF15, // XF86AudioStop
+		0xB3 => 0x1008FF14, // XF86AudioPlay
+		0xBA => 0x003B, // semicolon
+		0xBB => 0x003D, // equal
+		0xBC => 0x002C, // comma
+		0xBD => 0x002D, // minus
+		0xBE => 0x002E, // period
+		0xBF => 0x002F, // slash
+		0xC0 => 0x0060, // grave
+		0xDB => 0x005B, // bracketleft
+		0xDC => 0x005C, // backslash
+		0xDD => 0x005D, // bracketright
+		0xDE => 0x0027, // apostrophe
+		_ => return None
+	};
+	Some(keysym)
+}
\ No newline at end of file
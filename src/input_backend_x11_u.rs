@@ -0,0 +1,66 @@
+#[cfg(all(test, target_os="linux", feature="x11"))]
+mod tests {
+	use crate::{ InputBackend, InputBuilder, X11Backend, input_backend_x11::key_code_to_keysym, keys };
+	use x11_dl::xlib::{ Display, Xlib };
+	use std::{ os::raw::c_char, ptr, thread, time::{ Duration, Instant } };
+
+
+
+	/// Check if a key is held on the X11 server, according to the keymap of another connection.
+	fn key_held(xlib:&Xlib, display:*mut Display, key_code:u8) -> bool {
+		let mut keymap:[c_char; 32] = [0; 32];
+		unsafe {
+			let keycode:usize = (xlib.XKeysymToKeycode)(display, key_code_to_keysym(key_code).unwrap()) as usize;
+			(xlib.XQueryKeymap)(display, keymap.as_mut_ptr());
+			keymap[keycode / 8] as u8 & (1 << (keycode % 8)) != 0
+		}
+	}
+
+	/// Wait until a condition is true or the timeout expires, as the server handles the events of the connections in any order. Returns the final result of the condition.
+	fn wait_for<T>(timeout:Duration, condition:T) -> bool where T:Fn() -> bool {
+		let start:Instant = Instant::now();
+		while !condition() && start.elapsed() < timeout {
+			thread::sleep(Duration::from_millis(5));
+		}
+		condition()
+	}
+
+
+
+	#[test]
+	fn test_key_code_to_keysym() {
+		assert_eq!(key_code_to_keysym(keys::A.key_code()), Some(0x61));
+		assert_eq!(key_code_to_keysym(keys::Z.key_code()), Some(0x7A));
+		assert_eq!(key_code_to_keysym(keys::KEY_5.key_code()), Some(0x35));
+		assert_eq!(key_code_to_keysym(keys::F1.key_code()), Some(0xFFBE));
+		assert_eq!(key_code_to_keysym(keys::F24.key_code()), Some(0xFFD5));
+		assert_eq!(key_code_to_keysym(keys::LCONTROL.key_code()), Some(0xFFE3));
+		assert_eq!(key_code_to_keysym(keys::COMMA.key_code()), Some(0x2C));
+		assert_eq!(key_code_to_keysym(keys::LBUTTON.key_code()), None);
+	}
+
+	#[test]
+	#[ignore = "requires an X11 server, run with 'xvfb-run cargo test --features x11 -- --ignored'"]
+	fn test_mouse_movement() {
+		let backend:X11Backend = X11Backend::new().unwrap();
+		InputBuilder::new().with_mouse_move([10, 20]).execute_with(&backend);
+		assert_eq!(backend.mouse_position(), [10, 20]);
+		InputBuilder::new().with_mouse_displacement([5, -5]).execute_with(&backend);
+		assert_eq!(backend.mouse_position(), [15, 15]);
+	}
+
+	#[test]
+	#[ignore = "requires an X11 server, run with 'xvfb-run cargo test --features x11 -- --ignored'"]
+	fn test_key_send() {
+		let backend:X11Backend = X11Backend::new().unwrap();
+		let xlib:Xlib = Xlib::open().unwrap();
+		let display:*mut Display = unsafe { (xlib.XOpenDisplay)(ptr::null()) };
+		assert!(!display.is_null());
+
+		InputBuilder::new().with_press(&keys::LSHIFT).execute_with(&backend);
+		assert!(wait_for(Duration::from_secs(2), || key_held(&xlib, display, keys::LSHIFT.key_code())));
+		InputBuilder::new().with_release(&keys::LSHIFT).with_mouse_wheel(1).execute_with(&backend);
+		assert!(wait_for(Duration::from_secs(2), || !key_held(&xlib, display, keys::LSHIFT.key_code())));
+		unsafe { (xlib.XCloseDisplay)(display); }
+	}
+}
\ No newline at end of file
@@ -1,14 +1,11 @@
-use cachew::cache;
-use winapi::um::winuser::{ GetSystemMetrics, INPUT, INPUT_MOUSE, KEYBDINPUT, KEYEVENTF_KEYUP, MOUSEEVENTF_ABSOLUTE, MOUSEEVENTF_LEFTDOWN, MOUSEEVENTF_LEFTUP, MOUSEEVENTF_MIDDLEDOWN, MOUSEEVENTF_MIDDLEUP, MOUSEEVENTF_MOVE, MOUSEEVENTF_RIGHTDOWN, MOUSEEVENTF_RIGHTUP, MOUSEEVENTF_VIRTUALDESK, MOUSEEVENTF_XDOWN, MOUSEEVENTF_XUP, MOUSEINPUT, MapVirtualKeyW, SM_CXSCREEN, SM_CYSCREEN, SendInput };
-use crate::{ Key, KeyPattern, key_hook::handle_virtual_key_alteration, sleep };
-use std::{ mem, ptr, thread, time::Duration };
+use crate::{ InputAction, InputBackend, InputExecution, Key, KeyPattern, input_backend, input_execution::ExecutionFinisher, key_hook::handle_virtual_key_alteration };
+use std::thread;
 
 
 
 #[derive(Clone)]
 enum InputBuilderInput {
-	KeyInput((INPUT, u8, bool)),
-	MouseInput(INPUT),
+	Action(InputAction),
 	Delay(u64)
 }
 impl InputBuilderInput {
@@ -18,10 +15,9 @@ impl InputBuilderInput {
 			_ => 0
 		}
 	}
-	fn raw_input(&self) -> Option<&INPUT> {
+	fn action(&self) -> Option<&InputAction> {
 		match self {
-			InputBuilderInput::KeyInput((input, _, _)) => Some(input),
-			InputBuilderInput::MouseInput(input) => Some(input),
+			InputBuilderInput::Action(action) => Some(action),
 			InputBuilderInput::Delay(_) => None
 		}
 	}
@@ -95,6 +91,12 @@ impl InputBuilder {
 		self
 	}
 
+	/// Return self with an additional mouse wheel scroll.
+	pub fn with_mouse_wheel(mut self, delta:i32) -> Self {
+		self.add_mouse_wheel(delta);
+		self
+	}
+
 
 
 	/* INPUT ADDITION METHODS */
@@ -137,16 +139,17 @@ impl InputBuilder {
 	
 	/// Add a mouse displacement input.
 	pub fn add_mouse_displacement(&mut self, offset:[i32; 2]) {
-		self.add_raw_mouse_input(MOUSEEVENTF_MOVE, offset[0], offset[1])
+		self.inputs.push(InputBuilderInput::Action(InputAction::MouseDisplace(offset)));
 	}
 
 	/// Add a mouse move input.
 	pub fn add_mouse_move(&mut self, target_position:[i32; 2]) {
-		const POSITION_MULTIPLIER:i32 = 65535;
+		self.inputs.push(InputBuilderInput::Action(InputAction::MouseMove(target_position)));
+	}
 
-		let screen_size:&[i32; 2] = cache!([i32; 2], [GetSystemMetrics(SM_CXSCREEN), GetSystemMetrics(SM_CYSCREEN)]);
-		let normalized_position:[i32; 2] = [target_position[0] * POSITION_MULTIPLIER / screen_size[0], target_position[1] * POSITION_MULTIPLIER / screen_size[1]];
-		self.add_raw_mouse_input(MOUSEEVENTF_MOVE | MOUSEEVENTF_ABSOLUTE | MOUSEEVENTF_VIRTUALDESK, normalized_position[0], normalized_position[1]);
+	/// Add a mouse wheel input. Scrolls the given amount of notches, positive values scroll away from the user.
+	pub fn add_mouse_wheel(&mut self, delta:i32) {
+		self.inputs.push(InputBuilderInput::Action(InputAction::MouseWheel(delta)));
 	}
 	
 
@@ -154,14 +157,24 @@ impl InputBuilder {
 
 	/* USAGE METHODS */
 
-	/// Send the inputs in a separate thread.
-	pub fn execute_async(&self) {
+	/// Send the inputs on a dedicated thread, spawned for this execution. The returned execution can be awaited without blocking the executor or waited for, or dropped to let the inputs finish in the background.
+	pub fn execute_async(&self) -> InputExecution {
 		let clone:InputBuilder = self.clone();
-		thread::spawn(move || clone.execute());
+		let (execution, finisher):(InputExecution, ExecutionFinisher) = InputExecution::new();
+		thread::spawn(move || {
+			clone.execute();
+			drop(finisher);
+		});
+		execution
 	}
 
 	/// Send the inputs and wait for all of them to finish.
 	pub fn execute(&self) {
+		self.execute_with(&*input_backend());
+	}
+
+	/// Send the inputs through a specific backend and wait for all of them to finish.
+	pub fn execute_with(&self, backend:&dyn InputBackend) {
 		let mut cursor:usize = 0;
 		while cursor < self.inputs.len() {
 
@@ -169,20 +182,17 @@ impl InputBuilder {
 			let next_delay_index:usize = self.inputs.iter().skip(cursor).position(|input| input.delay() != 0).unwrap_or(self.inputs.len() - 1);
 			let execution_group:Vec<&InputBuilderInput> = self.inputs.iter().skip(cursor).take(next_delay_index + 1).collect();
 			let delay_after_execution:u64 = execution_group.last().map(|last| last.delay()).unwrap_or_default();
-			let inputs_to_execute:Vec<INPUT> = execution_group.iter().map(|input| input.raw_input()).flatten().cloned().collect();
-			Self::execute_inputs(inputs_to_execute);
+			let actions_to_execute:Vec<InputAction> = execution_group.iter().map(|input| input.action()).flatten().cloned().collect();
+			backend.send_actions(&actions_to_execute);
 
 			// Update virtual key-states.
-			for input in execution_group {
-				match input {
-					InputBuilderInput::KeyInput((_, key, down)) => handle_virtual_key_alteration(*key, *down),
-					_ => {}
-				}
+			for (key_code, down) in actions_to_execute.iter().filter_map(|action| action.key_alteration()) {
+				handle_virtual_key_alteration(key_code, down);
 			}
 
-			// Sleep any possible delays.
+			// Wait for any possible delays.
 			if delay_after_execution > 0 {
-				sleep(Duration::from_millis(delay_after_execution));
+				backend.delay(delay_after_execution);
 			}
 
 			// Move cursor to next execution group.
@@ -190,60 +200,14 @@ impl InputBuilder {
 		}
 	}
 
-	/// Execute the given inputs.
-	fn execute_inputs(mut inputs:Vec<INPUT>) {
-		unsafe { SendInput(inputs.len() as u32, inputs.as_mut_ptr(), mem::size_of::<INPUT>() as i32) };
-	}
-
 
 
 	/* RAW INPUT METHODS */
 
 	/// Add an input from core key-data.
-	#[allow(invalid_value)]
 	fn add_raw_key_inputs(&mut self, key:&dyn KeyOrKeyPattern, keys_down:bool) {
-		const MOUSE_DOWN_EVENTS:[u32; 5] = [MOUSEEVENTF_LEFTDOWN, MOUSEEVENTF_RIGHTDOWN, MOUSEEVENTF_MIDDLEDOWN, MOUSEEVENTF_XDOWN, MOUSEEVENTF_XDOWN];
-		const MOUSE_UP_EVENTS:[u32; 5] = [MOUSEEVENTF_LEFTUP, MOUSEEVENTF_RIGHTUP, MOUSEEVENTF_MIDDLEUP, MOUSEEVENTF_XUP, MOUSEEVENTF_XUP];
-
 		self.inputs.extend(
-			key.as_pattern().keys().into_iter().map(|key| {
-				let key_code:u8 = key.key_code();
-				InputBuilderInput::KeyInput((
-					unsafe {
-						if key_code < 6 {
-							let mut input_record:INPUT = INPUT { type_: INPUT_MOUSE, u: mem::MaybeUninit::uninit().assume_init() };
-							let event_list:&[u32; 5] = if keys_down { &MOUSE_DOWN_EVENTS } else { &MOUSE_UP_EVENTS };
-							let flags:u32 = event_list[key_code as usize - 1];
-							let input:MOUSEINPUT = MOUSEINPUT { dx: 0, dy: 0, mouseData: 0, dwFlags: flags, time: 0, dwExtraInfo: 0 };
-							ptr::write(&mut input_record.u as *mut _ as *mut MOUSEINPUT, input);
-							input_record
-						} else {
-							let mut input_record:INPUT = INPUT { type_: 1, u: mem::MaybeUninit::uninit().assume_init() };
-							let flags:u32 = if keys_down { 0 } else { KEYEVENTF_KEYUP };
-							let input:KEYBDINPUT = KEYBDINPUT { wVk: key_code as u16, wScan: MapVirtualKeyW(key_code as u32, 0) as u16, dwFlags: flags, time: 0, dwExtraInfo: 0 };
-							ptr::write(&mut input_record.u as *mut _ as *mut KEYBDINPUT, input);
-							input_record
-						}
-					},
-					key.key_code(),
-					keys_down
-				))
-			}).collect::<Vec<InputBuilderInput>>()
-		);
-	}
-
-	/// Add an input from core mouse-data.
-	#[allow(invalid_value)]
-	fn add_raw_mouse_input(&mut self, flags:u32, x:i32, y:i32) {
-		self.inputs.push(
-			InputBuilderInput::MouseInput(
-				unsafe {
-					let mut input_record:INPUT = INPUT { type_: INPUT_MOUSE, u: mem::MaybeUninit::uninit().assume_init() };
-					let input:MOUSEINPUT = MOUSEINPUT { dx: x, dy: y, mouseData: 0, dwFlags: flags, time: 0, dwExtraInfo: 0 };
-					ptr::write(&mut input_record.u as *mut _ as *mut MOUSEINPUT, input);
-					input_record
-				}
-			)
+			key.as_pattern().keys().iter().map(|key| InputBuilderInput::Action(InputAction::from_key(key, keys_down)))
 		);
 	}
 }
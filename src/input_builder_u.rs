@@ -0,0 +1,114 @@
+#[cfg(test)]
+pub(crate) mod tests {
+	use crate::{ InputAction, InputBackend, InputBuilder, InputExecution, KeyPattern, MouseButton, RecordedAction, RecordingBackend, keys, mouse, set_input_backend };
+	use std::{ sync::Mutex, time::Duration };
+
+
+
+	// Tests that use the global input backend cannot run simultaneously.
+	pub(crate) static GLOBAL_BACKEND_LOCK:Mutex<()> = Mutex::new(());
+
+	/// Create a recording backend and set it as the global input backend.
+	pub(crate) fn global_recorder() -> RecordingBackend {
+		let recorder:RecordingBackend = RecordingBackend::new();
+		set_input_backend(recorder.clone());
+		recorder
+	}
+
+
+
+	#[test]
+	fn test_send_pattern_timing() {
+		let recorder:RecordingBackend = RecordingBackend::new();
+		let pattern:KeyPattern = keys::CONTROL.pattern() | keys::C;
+		InputBuilder::new().with_send(&pattern, 100).execute_with(&recorder);
+		assert_eq!(recorder.actions(), vec![
+			RecordedAction::new(0, InputAction::KeyDown(keys::CONTROL.key_code())),
+			RecordedAction::new(0, InputAction::KeyDown(keys::C.key_code())),
+			RecordedAction::new(100, InputAction::KeyUp(keys::CONTROL.key_code())),
+			RecordedAction::new(100, InputAction::KeyUp(keys::C.key_code()))
+		]);
+		assert_eq!(recorder.time_millis(), 100);
+	}
+
+	#[test]
+	fn test_send_str_timing() {
+		let recorder:RecordingBackend = RecordingBackend::new();
+		InputBuilder::new().with_send_str("aB", 10).execute_with(&recorder);
+		assert_eq!(recorder.actions(), vec![
+			RecordedAction::new(0, InputAction::KeyDown(keys::A.key_code())),
+			RecordedAction::new(10, InputAction::KeyUp(keys::A.key_code())),
+			RecordedAction::new(10, InputAction::KeyDown(keys::SHIFT.key_code())),
+			RecordedAction::new(10, InputAction::KeyDown(keys::B.key_code())),
+			RecordedAction::new(20, InputAction::KeyUp(keys::SHIFT.key_code())),
+			RecordedAction::new(20, InputAction::KeyUp(keys::B.key_code()))
+		]);
+	}
+
+	#[test]
+	fn test_mouse_buttons_and_wheel() {
+		let recorder:RecordingBackend = RecordingBackend::new();
+		InputBuilder::new().with_click(&keys::RBUTTON).with_delay(5).with_mouse_wheel(-2).with_mouse_displacement([3, 4]).execute_with(&recorder);
+		assert_eq!(recorder.actions(), vec![
+			RecordedAction::new(0, InputAction::ButtonDown(MouseButton::Right)),
+			RecordedAction::new(0, InputAction::ButtonUp(MouseButton::Right)),
+			RecordedAction::new(5, InputAction::MouseWheel(-2)),
+			RecordedAction::new(5, InputAction::MouseDisplace([3, 4]))
+		]);
+		assert_eq!(recorder.mouse_position(), [3, 4]);
+	}
+
+	#[test]
+	fn test_mouse_click() {
+		let _lock = GLOBAL_BACKEND_LOCK.lock().unwrap();
+		let recorder:RecordingBackend = global_recorder();
+		recorder.set_mouse_position([5, 5]);
+		mouse::click([10, 20], Duration::from_millis(50));
+		assert_eq!(recorder.actions(), vec![
+			RecordedAction::new(0, InputAction::MouseMove([10, 20])),
+			RecordedAction::new(0, InputAction::ButtonDown(MouseButton::Left)),
+			RecordedAction::new(50, InputAction::ButtonUp(MouseButton::Left)),
+			RecordedAction::new(50, InputAction::MouseMove([5, 5]))
+		]);
+	}
+
+	#[test]
+	fn test_mouse_drag() {
+		let _lock = GLOBAL_BACKEND_LOCK.lock().unwrap();
+		let recorder:RecordingBackend = global_recorder();
+		mouse::drag([0, 0], [30, 60], Duration::from_millis(3));
+		assert_eq!(recorder.actions(), vec![
+			RecordedAction::new(0, InputAction::MouseMove([0, 0])),
+			RecordedAction::new(0, InputAction::ButtonDown(MouseButton::Left)),
+			RecordedAction::new(0, InputAction::MouseMove([0, 0])),
+			RecordedAction::new(1, InputAction::MouseMove([10, 20])),
+			RecordedAction::new(2, InputAction::MouseMove([20, 40])),
+			RecordedAction::new(2, InputAction::ButtonUp(MouseButton::Left)),
+			RecordedAction::new(2, InputAction::MouseMove([30, 60]))
+		]);
+	}
+
+	#[test]
+	fn test_execute_async_wait() {
+		let _lock = GLOBAL_BACKEND_LOCK.lock().unwrap();
+		let recorder:RecordingBackend = global_recorder();
+		let execution:InputExecution = InputBuilder::new().with_send(&keys::F1, 100).execute_async();
+		execution.wait();
+		assert_eq!(recorder.actions(), vec![
+			RecordedAction::new(0, InputAction::KeyDown(keys::F1.key_code())),
+			RecordedAction::new(100, InputAction::KeyUp(keys::F1.key_code()))
+		]);
+	}
+
+	#[cfg(feature="async")]
+	#[test]
+	fn test_execute_async_await() {
+		let _lock = GLOBAL_BACKEND_LOCK.lock().unwrap();
+		let recorder:RecordingBackend = global_recorder();
+		futures::executor::block_on(async {
+			InputBuilder::new().with_click(&keys::F2).with_delay(20).with_click(&keys::F3).execute_async().await;
+		});
+		assert_eq!(recorder.actions().len(), 4);
+		assert_eq!(recorder.time_millis(), 20);
+	}
+}
\ No newline at end of file
@@ -0,0 +1,62 @@
+use std::time::SystemTime;
+use crate::Key;
+
+
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum InputEventKind {
+	/// A key or mouse button being pressed or released.
+	Key { key:Key, down:bool },
+	/// The cursor moved to the mouse position of the event. Reported by the Windows hook and by absolute evdev devices, like tablets.
+	MouseMove,
+	/// The mouse moved by an offset. Reported by relative evdev devices, like mice.
+	MouseDisplace([i32; 2]),
+	/// Notches the wheel scrolled. Positive values scroll away from the user.
+	MouseWheel(i32),
+	/// Notches the horizontal wheel scrolled. Positive values scroll to the right.
+	MouseHorizontalWheel(i32)
+}
+
+
+
+#[derive(Clone, Copy, Debug)]
+pub struct InputEvent {
+	/// What changed.
+	pub kind:InputEventKind,
+	/// Whether the event was injected by software, instead of caused by a physical device.
+	pub injected:bool,
+	/// The time of the event.
+	pub timestamp:SystemTime,
+	/// The position of the cursor at the time of the event.
+	pub mouse_position:[i32; 2]
+}
+impl InputEvent {
+
+	/// Create a new input event.
+	pub fn new(kind:InputEventKind, injected:bool, timestamp:SystemTime, mouse_position:[i32; 2]) -> InputEvent {
+		InputEvent {
+			kind,
+			injected,
+			timestamp,
+			mouse_position
+		}
+	}
+
+	/// Create a new event of a key or mouse button being pressed or released.
+	pub fn key(key:Key, down:bool, injected:bool, timestamp:SystemTime, mouse_position:[i32; 2]) -> InputEvent {
+		InputEvent::new(InputEventKind::Key { key, down }, injected, timestamp, mouse_position)
+	}
+
+	/// If the event is a key or mouse button being pressed or released, get the key and the new state.
+	pub fn key_alteration(&self) -> Option<(Key, bool)> {
+		match self.kind {
+			InputEventKind::Key { key, down } => Some((key, down)),
+			_ => None
+		}
+	}
+
+	/// Whether the event was caused by a physical device, as opposed to being injected by software.
+	pub fn physical(&self) -> bool {
+		!self.injected
+	}
+}
\ No newline at end of file
@@ -0,0 +1,76 @@
+use std::{ future::Future, pin::Pin, sync::{ Arc, Condvar, Mutex, MutexGuard }, task::{ Context, Poll, Waker } };
+
+
+
+#[derive(Default)]
+struct ExecutionState {
+	finished:bool,
+	waker:Option<Waker>
+}
+
+
+
+/// A handle to inputs being sent on a dedicated thread. Awaiting the handle waits for all inputs to finish without blocking the executor. Dropping the handle does not stop the inputs.
+#[must_use = "the inputs keep being sent in the background, await or wait for the execution to know when they finished"]
+pub struct InputExecution {
+	state:Arc<(Mutex<ExecutionState>, Condvar)>
+}
+impl InputExecution {
+
+	/* CONSTRUCTOR METHODS */
+
+	/// Create a new unfinished execution and the finisher that marks it as finished when dropped.
+	pub(crate) fn new() -> (InputExecution, ExecutionFinisher) {
+		let state:Arc<(Mutex<ExecutionState>, Condvar)> = Arc::new((Mutex::new(ExecutionState::default()), Condvar::new()));
+		(InputExecution { state: state.clone() }, ExecutionFinisher { state })
+	}
+
+
+
+	/* USAGE METHODS */
+
+	/// Wether or not all inputs have been sent.
+	pub fn finished(&self) -> bool {
+		self.state.0.lock().unwrap().finished
+	}
+
+	/// Block the current thread until all inputs have been sent.
+	pub fn wait(self) {
+		let (state, finished_condition) = &*self.state;
+		let mut state:MutexGuard<'_, ExecutionState> = state.lock().unwrap();
+		while !state.finished {
+			state = finished_condition.wait(state).unwrap();
+		}
+	}
+}
+impl Future for InputExecution {
+	type Output = ();
+
+	fn poll(self:Pin<&mut Self>, context:&mut Context<'_>) -> Poll<()> {
+		let mut state:MutexGuard<'_, ExecutionState> = self.state.0.lock().unwrap();
+		if state.finished {
+			Poll::Ready(())
+		} else {
+			state.waker = Some(context.waker().clone());
+			Poll::Pending
+		}
+	}
+}
+
+
+
+/// Marks an execution as finished when dropped, so the execution also finishes when the inputs panic.
+pub(crate) struct ExecutionFinisher {
+	state:Arc<(Mutex<ExecutionState>, Condvar)>
+}
+impl Drop for ExecutionFinisher {
+	fn drop(&mut self) {
+		let (state, finished_condition) = &*self.state;
+		let mut state:MutexGuard<'_, ExecutionState> = state.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+		state.finished = true;
+		if let Some(waker) = state.waker.take() {
+			waker.wake();
+		}
+		finished_condition.notify_all();
+	}
+}
\ No newline at end of file
@@ -1,4 +1,4 @@
-use crate::{ KeyPattern, key_hook, keys };
+use crate::{ InputExecution, KeyPattern, key_hook, keys };
 use mini_rand::Randomizable;
 use std::time::Duration;
 
@@ -97,6 +97,17 @@ impl Key {
 		}
 	}
 
+	/// Try to get the character the key types, the reverse of `from_char`. Shifted selects the character typed while shift is held.
+	pub fn to_char(&self, shifted:bool) -> Option<char> {
+		(' '..='~').chain(['\t', '\n']).find(|character| {
+			match Key::from_char(*character).as_slice() {
+				[key] => !shifted && key.code == self.code,
+				[modifier, key] => shifted && modifier.code == keys::SHIFT.code && key.code == self.code,
+				_ => false
+			}
+		})
+	}
+
 
 
 	/* PROPERTY GETTER METHODS */
@@ -146,9 +157,9 @@ impl Key {
 		self.pattern.send(duration);
 	}
 
-	/// Send the key in a separate thread.
-	pub fn send_async<T>(&self, duration:T) where T:Randomizable<Duration> {
-		self.pattern.send_async(duration);
+	/// Send the key in a separate thread. The returned execution can be awaited.
+	pub fn send_async<T>(&self, duration:T) -> InputExecution where T:Randomizable<Duration> {
+		self.pattern.send_async(duration)
 	}
 }
 impl PartialEq for Key {
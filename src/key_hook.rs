@@ -1,132 +1,207 @@
-use winapi::{ shared::{ minwindef::{ LPARAM, LRESULT, WPARAM }, windef::HHOOK__ }, um::winuser::{ CallNextHookEx, KBDLLHOOKSTRUCT, LLKHF_INJECTED, LLMHF_INJECTED, MSLLHOOKSTRUCT, WM_KEYDOWN, WM_KEYUP } };
-use std::{ ptr, sync::{ Mutex, MutexGuard } };
-use crate::{ Key, KeyOrKeyPattern, KeyPattern };
+use std::{ error::Error, fmt::{ self, Display, Formatter }, io, sync::{ Mutex, atomic::{ AtomicBool, Ordering }, mpsc::{ self, Receiver, Sender } } };
+use crate::{ InputEvent, key_state_store::KeyStateStore };
+#[cfg(feature="async")]
+use futures::channel::mpsc::{ UnboundedReceiver, UnboundedSender };
+#[cfg(target_os="linux")]
+pub use crate::key_hook_evdev::read_events;
 
 
 
-static LISTENER_THREAD_ID:Mutex<Option<u32>> = Mutex::new(None);
-pub(crate) static mut PHYSICAL_KEY_STATES:KeyPattern = KeyPattern::zero(); // Used incredibly much, only has one mutable thread and has a static size and address, so do not use mutex.
-pub(crate) static mut VIRTUAL_KEY_STATES:KeyPattern = KeyPattern::zero(); // Used incredibly much, only has one mutable thread and has a static size and address, so do not use mutex.
+pub(crate) static PHYSICAL_KEY_STATES:KeyStateStore = KeyStateStore::new(); // Used incredibly much, so uses atomics instead of a mutex.
+pub(crate) static VIRTUAL_KEY_STATES:KeyStateStore = KeyStateStore::new(); // Used incredibly much, so uses atomics instead of a mutex.
+static HOOK_INSTALLED:AtomicBool = AtomicBool::new(false);
+static CAN_BLOCK:AtomicBool = AtomicBool::new(cfg!(windows)); // Only the Windows hook can stop events from reaching other processes.
+static EVENT_SUBSCRIBERS:Mutex<Vec<EventSubscriber>> = Mutex::new(Vec::new());
 
 
 
-/* HOOK INSTALLATION METHODS */
+#[derive(Debug)]
+pub enum HookError {
+	AlreadyInstalled,
+	InstallFailed(String),
+	Io(io::Error)
+}
+impl Display for HookError {
+	fn fmt(&self, f:&mut Formatter<'_>) -> fmt::Result {
+		match self {
+			HookError::AlreadyInstalled => write!(f, "The key hook is already installed."),
+			HookError::InstallFailed(reason) => write!(f, "Could not install the key hook: {reason}"),
+			HookError::Io(error) => write!(f, "Could not install the key hook: {error}")
+		}
+	}
+}
+impl Error for HookError {}
+impl From<io::Error> for HookError {
+	fn from(error:io::Error) -> HookError {
+		HookError::Io(error)
+	}
+}
+
 
-/// Install the mouse and keyboard hook.
-pub fn install() {
-	use winapi::um::{ processthreadsapi::GetCurrentThreadId, winuser::{ SetWindowsHookExW, WH_MOUSE_LL, WH_KEYBOARD_LL, GetMessageW } };
-	use std::{ thread, ptr::null_mut };
-
-	// Create activate listener thread if not exists.
-	thread::spawn(|| unsafe {
-		let mut listener_thread_id:MutexGuard<'_, Option<u32>> = LISTENER_THREAD_ID.lock().unwrap();
-		if listener_thread_id.is_none() {
-			*listener_thread_id = Some(GetCurrentThreadId());
-	
-			// Create new hooks
-			let hook_mouse:*mut HHOOK__ = SetWindowsHookExW(WH_MOUSE_LL, Some(hook_callback), null_mut(), 0);
-			let hook_keyboard:*mut HHOOK__  = SetWindowsHookExW(WH_KEYBOARD_LL, Some(hook_callback), null_mut(), 0);
-			if hook_mouse.is_null() {
-				panic!("Failed to install mouse hook");
-			}
-			if hook_keyboard.is_null() {
-				panic!("Failed to install keyboard hook");
-			}
-			
-			// Start the message listener.
-			GetMessageW(null_mut(), null_mut(), 0, 0);
+
+enum EventSubscriber {
+	Channel(Sender<InputEvent>),
+	#[cfg(feature="async")]
+	Stream(UnboundedSender<InputEvent>)
+}
+impl EventSubscriber {
+
+	/// Send an event to the subscriber. Returns false if the subscriber no longer receives events.
+	fn send(&self, event:InputEvent) -> bool {
+		match self {
+			EventSubscriber::Channel(sender) => sender.send(event).is_ok(),
+			#[cfg(feature="async")]
+			EventSubscriber::Stream(sender) => sender.unbounded_send(event).is_ok()
 		}
-	});
+	}
 }
 
 
 
-/* HOOK HANDLING METHODS */
+/// Keeps the key hook installed. The hook is uninstalled and its listener is joined when the guard is dropped.
+#[must_use = "the key hook is uninstalled when the guard is dropped"]
+pub struct HookGuard {
+	stop_listener:Option<Box<dyn FnOnce() + Send>>
+}
+impl HookGuard {
 
-/// The callback to catch the pressed keys.
-#[allow(static_mut_refs)]
-unsafe extern "system" fn hook_callback(key_code:i32, w_param:WPARAM, l_param:LPARAM) -> LRESULT {
-	use crate::hokey::REGISTERED_HOTKEYS;
-
-	let w_param:usize = w_param as usize;
-	let mut blocking:bool = false;
-
-	// Find key id and state change from arguments.
-	if key_code >= 0 {
-		if let Some((key_code, down)) = params_to_key_alteration(w_param as u32, l_param) {
-			handle_key_alteration(key_code, down);
-
-			// Update hotkeys.
-			let state_change_pattern:KeyPattern = Key::new(key_code).as_pattern();
-			for hotkey in unsafe { &mut REGISTERED_HOTKEYS } {
-				if hotkey.update_state(state_change_pattern, unsafe { &PHYSICAL_KEY_STATES }) {
-					blocking = true;
-				}
-			}
+	/// Uninstall the hook and wait for its listener to stop.
+	pub fn uninstall(self) {
+		drop(self);
+	}
+}
+impl Drop for HookGuard {
+	fn drop(&mut self) {
+		if let Some(stop_listener) = self.stop_listener.take() {
+			stop_listener();
 		}
+		HOOK_INSTALLED.store(false, Ordering::SeqCst);
 	}
+}
 
-	// Move on to next callback.
-	if blocking {
-		1
-	} else {
-		unsafe { CallNextHookEx(ptr::null_mut(), key_code, w_param, l_param) }
-	}
+
+
+/* HOOK INSTALLATION METHODS */
+
+/// Install the mouse and keyboard hook. The hook stays installed until the returned guard is dropped.
+#[cfg(windows)]
+pub fn install() -> Result<HookGuard, HookError> {
+	install_with(crate::key_hook_windows::start_hook)
 }
 
-/// Figure out a pressed key-code and a boolean indicating the key being pressed or not from hook callback arguments.
-fn params_to_key_alteration(w_param:u32, l_param:LPARAM) -> Option<(u8, bool)> {
+/// Install the mouse and keyboard listener. Reads all event devices in '/dev/input' the process has access to, which usually requires the user to be in the 'input' group.
+/// Unlike the Windows hook, the listener cannot stop other processes from receiving events, so blocking hotkeys do not block.
+/// The listener stays installed until the returned guard is dropped.
+#[cfg(target_os="linux")]
+pub fn install() -> Result<HookGuard, HookError> {
+	install_with(crate::key_hook_evdev::start_device_listeners)
+}
 
-	// Keyboard
-	if w_param == WM_KEYDOWN || w_param == WM_KEYUP {
-		let kbd:&KBDLLHOOKSTRUCT = unsafe { &*(l_param as *const KBDLLHOOKSTRUCT) };
-		if kbd.flags & LLKHF_INJECTED == 0 {
-			return Some((kbd.vkCode as u8, w_param == WM_KEYDOWN));
-		}
-	}
+/// Install a listener reading evdev events from a specific path, like an event device, a pipe or a recorded capture file. The listener stays installed until the returned guard is dropped.
+#[cfg(target_os="linux")]
+pub fn install_from_path<T>(path:T) -> Result<HookGuard, HookError> where T:AsRef<std::path::Path> {
+	install_with(|| crate::key_hook_evdev::start_path_listener(path.as_ref()))
+}
 
-	// Mouse 
-	else if (0x201..0x20C).contains(&w_param) {
-		let md:MSLLHOOKSTRUCT = unsafe { *(l_param as *const MSLLHOOKSTRUCT) };
-		if md.flags & LLMHF_INJECTED == 0 {
-			return match w_param {
-				0x201 => Some((0x01, true)),
-				0x202 => Some((0x01, false)),
-				0x204 => Some((0x02, true)),
-				0x205 => Some((0x02, false)),
-				0x207 => Some((0x03, true)),
-				0x208 => Some((0x03, false)),
-				_ => None
-			};
+/// Install a hook using the given starting function, which returns a function that stops the hook. Only one hook can be installed at a time.
+#[cfg(any(windows, target_os="linux"))]
+fn install_with<T>(start_hook:T) -> Result<HookGuard, HookError> where T:FnOnce() -> Result<Box<dyn FnOnce() + Send>, HookError> {
+	if HOOK_INSTALLED.swap(true, Ordering::SeqCst) {
+		return Err(HookError::AlreadyInstalled);
+	}
+	match start_hook() {
+		Ok(stop_listener) => Ok(HookGuard { stop_listener: Some(stop_listener) }),
+		Err(error) => {
+			HOOK_INSTALLED.store(false, Ordering::SeqCst);
+			Err(error)
 		}
 	}
+}
+
+
+
+/* EVENT SUBSCRIPTION METHODS */
 
-	// No key found.
-	None
+/// Subscribe to all input events caught by the hook, including injected ones. The subscription ends when the receiver is dropped.
+pub fn subscribe() -> Receiver<InputEvent> {
+	let (sender, receiver):(Sender<InputEvent>, Receiver<InputEvent>) = mpsc::channel();
+	EVENT_SUBSCRIBERS.lock().unwrap().push(EventSubscriber::Channel(sender));
+	receiver
+}
+
+/// Subscribe to all input events caught by the hook as a stream, including injected ones. The subscription ends when the stream is dropped.
+#[cfg(feature="async")]
+pub fn event_stream() -> UnboundedReceiver<InputEvent> {
+	let (sender, receiver):(UnboundedSender<InputEvent>, UnboundedReceiver<InputEvent>) = futures::channel::mpsc::unbounded();
+	EVENT_SUBSCRIBERS.lock().unwrap().push(EventSubscriber::Stream(sender));
+	receiver
+}
+
+/// Whether anything is subscribed to input events. Allows hooks to skip gathering event data nobody receives.
+#[cfg(any(windows, target_os="linux"))]
+pub(crate) fn has_subscribers() -> bool {
+	!EVENT_SUBSCRIBERS.lock().unwrap().is_empty()
+}
+
+/// Send an event to all subscribers. Removes subscribers whose receiver was dropped.
+fn publish_event(event:&InputEvent) {
+	EVENT_SUBSCRIBERS.lock().unwrap().retain(|subscriber| subscriber.send(*event));
+}
+
+
+
+/* HOOK HANDLING METHODS */
+
+/// Whether the hook of this platform can stop key events from reaching other processes. Only the Windows hook can, on Linux blocking hotkeys still trigger but the events pass.
+pub fn can_block() -> bool {
+	CAN_BLOCK.load(Ordering::SeqCst)
+}
+
+/// Handle an event caught by any hook. Sends the event to all subscribers. Physical key events also update the key-states, the typed characters of hotstrings, the mod-taps and, unless a mod-tap handled them, all registered hotkeys. Returns true if an immediate hotstring or any of the hotkeys blocks the event.
+pub(crate) fn handle_hook_event(event:InputEvent) -> bool {
+	use crate::hotkey_registry::REGISTERED_HOTKEYS;
+
+	publish_event(&event);
+	let Some((key, down)) = event.key_alteration() else {
+		return false; // Mouse movement and wheel events are only published.
+	};
+	if event.injected {
+		return false;
+	}
+	handle_key_alteration(key.key_code(), down);
+	if crate::hotstring::handle_typed_key(key.key_code(), down) {
+		return true;
+	}
+	if let Some(blocking) = crate::mod_tap::handle_mod_taps(key.key_code(), down) {
+		return blocking;
+	}
+	REGISTERED_HOTKEYS.update(key.pattern(), &PHYSICAL_KEY_STATES.snapshot(), event.timestamp)
 }
 
 /// Handle a key being pressed or released.
-#[allow(static_mut_refs)]
 pub(crate) fn handle_key_alteration(key_code:u8, down:bool) {
-	unsafe {
-		if down {
-			PHYSICAL_KEY_STATES |= Key::new(key_code);
-		} else {
-			PHYSICAL_KEY_STATES &= !Key::new(key_code).pattern();
-		}
-	}
+	PHYSICAL_KEY_STATES.set(key_code, down);
 }
 
 /// Handle a virtual key being pressed or released.
-#[allow(static_mut_refs)]
 pub(crate) fn handle_virtual_key_alteration(key_code:u8, down:bool) {
-	unsafe {
-		if down {
-			VIRTUAL_KEY_STATES |= Key::new(key_code);
-		} else {
-			VIRTUAL_KEY_STATES &= !Key::new(key_code).pattern();
-		}
-	}
+	VIRTUAL_KEY_STATES.set(key_code, down);
+}
+
+
+
+/* SIMULATION METHODS */
+
+/// Set whether blocked events are assumed to be stopped, to test the behavior of hooks that cannot block.
+#[cfg(test)]
+pub(crate) fn set_can_block(can_block:bool) {
+	CAN_BLOCK.store(can_block, Ordering::SeqCst);
+}
+
+/// Simulate a physical key event without an OS hook. The event goes through the same path as events caught by the hook, updating the key-states and all registered hotkeys. Returns true if the hook would have blocked the event.
+#[cfg(any(test, feature="mock"))]
+pub fn simulate(key:crate::Key, down:bool) -> bool {
+	handle_hook_event(InputEvent::key(key, down, false, std::time::SystemTime::now(), crate::mouse::get_pos()))
 }
 
 
@@ -135,10 +210,10 @@ pub(crate) fn handle_virtual_key_alteration(key_code:u8, down:bool) {
 
 /// Get the key state of a key.
 pub fn get_key_state(key_code:u8) -> bool {
-	unsafe { PHYSICAL_KEY_STATES & Key::new(key_code).pattern() != KeyPattern::ZERO }
+	PHYSICAL_KEY_STATES.get(key_code)
 }
 
 /// Get the virtual key state of a key (programatically pressed).
 pub fn get_key_state_v(key_code:u8) -> bool {
-	unsafe { VIRTUAL_KEY_STATES & Key::new(key_code).pattern() != KeyPattern::ZERO }
+	VIRTUAL_KEY_STATES.get(key_code)
 }
\ No newline at end of file
@@ -0,0 +1,169 @@
+use std::{ fs::{ self, File }, io::{ self, ErrorKind, Read }, mem, os::fd::AsRawFd, path::{ Path, PathBuf }, sync::{ Arc, atomic::{ AtomicBool, Ordering } }, ptr, thread::{ self, JoinHandle }, time::{ Duration, SystemTime, UNIX_EPOCH } };
+use crate::{ InputEvent, InputEventKind, Key, evdev_codes::{ ABS_X, ABS_Y, EV_ABS, EV_KEY, EV_REL, EV_SYN, REL_HWHEEL, REL_WHEEL, REL_X, REL_Y, SYN_REPORT, VIRTUAL_DEVICE_NAME_PREFIX, evdev_to_key_code }, key_hook::{ HookError, handle_hook_event, has_subscribers } };
+
+
+
+const INPUT_DEVICES_DIR:&str = "/dev/input";
+const POLL_TIMEOUT_MILLIS:i32 = 50;
+const DEVICE_NAME_LENGTH:usize = 256;
+const EVIOCGNAME:libc::Ioctl = (2 << 30 | (DEVICE_NAME_LENGTH << 16) | (0x45 << 8) | 0x06) as libc::Ioctl;
+pub(crate) const INPUT_EVENT_SIZE:usize = mem::size_of::<libc::input_event>(); // A timeval, followed by a u16 type, a u16 code and an i32 value. The size of the timeval differs per target.
+
+
+
+/* HOOK INSTALLATION METHODS */
+
+/// Start a listener for each event device in '/dev/input' the process has access to. Returns a function that stops and joins all listeners.
+pub(crate) fn start_device_listeners() -> Result<Box<dyn FnOnce() + Send>, HookError> {
+	let device_paths:Vec<PathBuf> = fs::read_dir(INPUT_DEVICES_DIR)?.flatten().map(|entry| entry.path()).filter(|path| path.file_name().map(|name| name.to_string_lossy().starts_with("event")).unwrap_or(false)).collect();
+	let devices:Vec<(File, bool)> = device_paths.iter().flat_map(File::open).map(|device| {
+		let injected:bool = device_name(&device).map(|name| name.starts_with(VIRTUAL_DEVICE_NAME_PREFIX)).unwrap_or(false);
+		(device, injected)
+	}).collect();
+	if devices.is_empty() {
+		return Err(HookError::InstallFailed(format!("Could not open any input device in '{INPUT_DEVICES_DIR}'. Are you sure the user has access to them?")));
+	}
+	Ok(start_listeners(devices))
+}
+
+/// Start a listener for a specific path. All events read from it are considered physical. Returns a function that stops and joins the listener.
+pub(crate) fn start_path_listener(path:&Path) -> Result<Box<dyn FnOnce() + Send>, HookError> {
+	Ok(start_listeners(vec![(File::open(path)?, false)]))
+}
+
+/// Start a listener thread for each file and whether its events are injected. Returns a function that stops and joins all listeners.
+fn start_listeners(files:Vec<(File, bool)>) -> Box<dyn FnOnce() + Send> {
+	let stop:Arc<AtomicBool> = Arc::new(AtomicBool::new(false));
+	let listeners:Vec<JoinHandle<()>> = files.into_iter().map(|(file, injected)| {
+		let stop:Arc<AtomicBool> = stop.clone();
+		thread::spawn(move || listen(file, injected, &stop))
+	}).collect();
+	Box::new(move || {
+		stop.store(true, Ordering::SeqCst);
+		for listener in listeners {
+			let _ = listener.join();
+		}
+	})
+}
+
+/// Get the name of an event device.
+fn device_name(device:&File) -> Option<String> {
+	let mut name:[u8; DEVICE_NAME_LENGTH] = [0; DEVICE_NAME_LENGTH];
+	if unsafe { libc::ioctl(device.as_raw_fd(), EVIOCGNAME, name.as_mut_ptr()) } < 0 {
+		return None;
+	}
+	let name_length:usize = name.iter().position(|byte| *byte == 0).unwrap_or(DEVICE_NAME_LENGTH);
+	Some(String::from_utf8_lossy(&name[..name_length]).to_string())
+}
+
+
+
+/* HOOK HANDLING METHODS */
+
+/// Handle events from a file until it ends or the listener is stopped. Polls with a timeout, so the stop flag is checked even when no events arrive.
+fn listen(mut file:File, injected:bool, stop:&AtomicBool) {
+	let mut motion:PendingMotion = PendingMotion::default();
+	let mut poll_target:libc::pollfd = libc::pollfd { fd: file.as_raw_fd(), events: libc::POLLIN, revents: 0 };
+	while !stop.load(Ordering::SeqCst) {
+		match unsafe { libc::poll(&mut poll_target, 1, POLL_TIMEOUT_MILLIS) } {
+			0 => continue,
+			ready if ready < 0 => {
+				if io::Error::last_os_error().kind() == ErrorKind::Interrupted {
+					continue;
+				}
+				return;
+			},
+			_ => match read_event(&mut file) {
+				Ok(Some(event)) => handle_event(&event, injected, &mut motion),
+				_ => return
+			}
+		}
+	}
+}
+
+/// Read evdev events until the reader ends. Each event is handled the same way the hook handles physical events.
+pub fn read_events<T>(mut reader:T) -> io::Result<()> where T:Read {
+	let mut motion:PendingMotion = PendingMotion::default();
+	while let Some(event) = read_event(&mut reader)? {
+		handle_event(&event, false, &mut motion);
+	}
+	Ok(())
+}
+
+/// Read a single raw event. Returns None if the reader ended.
+fn read_event<T>(reader:&mut T) -> io::Result<Option<[u8; INPUT_EVENT_SIZE]>> where T:Read {
+	let mut event:[u8; INPUT_EVENT_SIZE] = [0; INPUT_EVENT_SIZE];
+	match reader.read_exact(&mut event) {
+		Ok(_) => Ok(Some(event)),
+		Err(error) if error.kind() == ErrorKind::UnexpectedEof => Ok(None),
+		Err(error) => Err(error)
+	}
+}
+
+/// Handle a raw event the same way the hook handles it. Mouse motion is collected until the device reports it is synchronized.
+fn handle_event(event:&[u8; INPUT_EVENT_SIZE], injected:bool, motion:&mut PendingMotion) {
+	let event:libc::input_event = unsafe { ptr::read_unaligned(event.as_ptr() as *const libc::input_event) };
+	if let Some((key_code, down)) = event_to_key_alteration(&event) {
+		let mouse_position:[i32; 2] = if has_subscribers() { crate::mouse::get_pos() } else { [0, 0] };
+		handle_hook_event(InputEvent::key(Key::new(key_code), down, injected, event_timestamp(&event), mouse_position));
+	} else if event.type_ == EV_SYN && event.code == SYN_REPORT {
+		let motion:PendingMotion = mem::take(motion);
+		if motion != PendingMotion::default() && has_subscribers() {
+			let mouse_position:[i32; 2] = crate::mouse::get_pos();
+			for kind in motion.event_kinds() {
+				handle_hook_event(InputEvent::new(kind, injected, event_timestamp(&event), mouse_position));
+			}
+		}
+	} else {
+		motion.add(&event);
+	}
+}
+
+/// Get the time an event happened.
+fn event_timestamp(event:&libc::input_event) -> SystemTime {
+	UNIX_EPOCH + Duration::from_secs(event.time.tv_sec as u64) + Duration::from_micros(event.time.tv_usec as u64)
+}
+
+/// Figure out a pressed key-code and a boolean indicating the key being pressed or not from an evdev event. Repeats count as presses, like they do in the Windows hook.
+fn event_to_key_alteration(event:&libc::input_event) -> Option<(u8, bool)> {
+	if event.type_ == EV_KEY {
+		evdev_to_key_code(event.code).map(|key_code| (key_code, event.value != 0))
+	} else {
+		None
+	}
+}
+
+
+
+/// Mouse motion of an evdev device since its last synchronization event.
+#[derive(Default, PartialEq)]
+struct PendingMotion {
+	displacement:[i32; 2],
+	moved:bool,
+	wheel:i32,
+	horizontal_wheel:i32
+}
+impl PendingMotion {
+
+	/// Add the motion of a relative or absolute event.
+	fn add(&mut self, event:&libc::input_event) {
+		match (event.type_, event.code) {
+			(EV_REL, REL_X) => self.displacement[0] += event.value,
+			(EV_REL, REL_Y) => self.displacement[1] += event.value,
+			(EV_REL, REL_WHEEL) => self.wheel += event.value,
+			(EV_REL, REL_HWHEEL) => self.horizontal_wheel += event.value,
+			(EV_ABS, ABS_X | ABS_Y) => self.moved = true,
+			_ => {}
+		}
+	}
+
+	/// Get the kinds of the events describing the motion.
+	fn event_kinds(&self) -> Vec<InputEventKind> {
+		[
+			(self.displacement != [0, 0]).then_some(InputEventKind::MouseDisplace(self.displacement)),
+			self.moved.then_some(InputEventKind::MouseMove),
+			(self.wheel != 0).then_some(InputEventKind::MouseWheel(self.wheel)),
+			(self.horizontal_wheel != 0).then_some(InputEventKind::MouseHorizontalWheel(self.horizontal_wheel))
+		].into_iter().flatten().collect()
+	}
+}
\ No newline at end of file
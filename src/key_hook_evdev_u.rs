@@ -0,0 +1,187 @@
+#[cfg(all(test, target_os="linux"))]
+mod tests {
+	use crate::{ Hotkey, HotkeyGuard, InputEvent, InputEventKind, Key, evdev_codes::{ ABS_X, EV_ABS, EV_KEY, EV_REL, EV_SYN, REL_HWHEEL, REL_WHEEL, REL_X, REL_Y, SYN_REPORT }, key_hook::{ self, HookError, HookGuard }, hokey_u::tests::HOTKEY_LOCK, key_hook_evdev::INPUT_EVENT_SIZE, keys };
+	use std::{ ffi::CString, fs, io::{ Cursor, Write }, mem, slice, sync::{ Arc, Mutex, MutexGuard, PoisonError, atomic::{ AtomicUsize, Ordering }, mpsc::Receiver }, thread, time::{ Duration, Instant, UNIX_EPOCH } };
+
+
+
+	static INSTALL_LOCK:Mutex<()> = Mutex::new(()); // Only one hook can be installed at a time. Events read by any test update the registered hotkeys, so tests also take the hotkey lock first.
+
+
+
+	/// Create a raw evdev event.
+	fn raw_event(event_type:u16, code:u16, value:i32) -> Vec<u8> {
+		raw_event_at(0, 0, event_type, code, value)
+	}
+
+	/// Create a raw evdev event that happened at a specific time.
+	fn raw_event_at(seconds:i64, micros:i64, event_type:u16, code:u16, value:i32) -> Vec<u8> {
+		let mut event:libc::input_event = unsafe { mem::zeroed() };
+		event.time.tv_sec = seconds as libc::time_t;
+		event.time.tv_usec = micros as libc::suseconds_t;
+		event.type_ = event_type;
+		event.code = code;
+		event.value = value;
+		unsafe { slice::from_raw_parts(&event as *const libc::input_event as *const u8, INPUT_EVENT_SIZE) }.to_vec()
+	}
+
+	/// Create a capture of raw key events, each followed by a synchronization event.
+	fn key_capture(events:&[(u16, i32)]) -> Vec<u8> {
+		events.iter().map(|(code, value)| [raw_event(EV_KEY, *code, *value), raw_event(EV_SYN, 0, 0)].concat()).collect::<Vec<Vec<u8>>>().concat()
+	}
+
+	/// Get a unique path in the temporary directory.
+	fn temp_path(name:&str) -> String {
+		format!("{}/key_flow_{name}_{}", std::env::temp_dir().display(), std::process::id())
+	}
+
+	/// Wait until a condition is true or the timeout expires. Returns the final result of the condition.
+	fn wait_for<T>(timeout:Duration, condition:T) -> bool where T:Fn() -> bool {
+		let start:Instant = Instant::now();
+		while !condition() && start.elapsed() < timeout {
+			thread::sleep(Duration::from_millis(5));
+		}
+		condition()
+	}
+
+
+
+	#[test]
+	fn test_read_key_states() {
+		const KEY_DELETE:u16 = 111;
+
+		let _hotkey_lock:MutexGuard<'_, ()> = HOTKEY_LOCK.lock().unwrap();
+		key_hook::read_events(Cursor::new(key_capture(&[(KEY_DELETE, 1)]))).unwrap();
+		assert!(keys::DELETE.down());
+		key_hook::read_events(Cursor::new(key_capture(&[(KEY_DELETE, 2)]))).unwrap();
+		assert!(keys::DELETE.down());
+		key_hook::read_events(Cursor::new(key_capture(&[(KEY_DELETE, 0)]))).unwrap();
+		assert!(!keys::DELETE.down());
+	}
+
+	#[test]
+	fn test_read_ignores_other_events() {
+		const KEY_END:u16 = 107;
+
+		let _hotkey_lock:MutexGuard<'_, ()> = HOTKEY_LOCK.lock().unwrap();
+		let mut capture:Vec<u8> = raw_event(EV_REL, KEY_END, 1);
+		capture.extend(raw_event(EV_SYN, KEY_END, 1));
+		capture.extend(raw_event(EV_KEY, 0x2FF, 1)); // Not a known key.
+		key_hook::read_events(Cursor::new(capture)).unwrap();
+		assert!(!keys::END.down());
+	}
+
+	#[test]
+	fn test_read_triggers_hotkey() {
+		const KEY_LEFTCTRL:u16 = 29;
+		const KEY_NEXTSONG:u16 = 163;
+
+		let _hotkey_lock:MutexGuard<'_, ()> = HOTKEY_LOCK.lock().unwrap();
+		let counters:Arc<[AtomicUsize; 3]> = Arc::new([AtomicUsize::new(0), AtomicUsize::new(0), AtomicUsize::new(0)]);
+		let (press_counters, repeat_counters, release_counters) = (counters.clone(), counters.clone(), counters.clone());
+		let _guard:HotkeyGuard = Hotkey::new(&[keys::LCONTROL, keys::MEDIA_NEXT_TRACK])
+			.on_press(move || { press_counters[0].fetch_add(1, Ordering::SeqCst); })
+			.on_repeat(move || { repeat_counters[1].fetch_add(1, Ordering::SeqCst); })
+			.on_release(move || { release_counters[2].fetch_add(1, Ordering::SeqCst); })
+			.inline()
+			.register()
+			.unregister_on_drop();
+
+		key_hook::read_events(Cursor::new(key_capture(&[(KEY_LEFTCTRL, 1), (KEY_NEXTSONG, 1), (KEY_NEXTSONG, 2), (KEY_NEXTSONG, 2), (KEY_NEXTSONG, 0), (KEY_LEFTCTRL, 0)]))).unwrap();
+		assert_eq!(counters.iter().map(|counter| counter.load(Ordering::SeqCst)).collect::<Vec<usize>>(), vec![1, 2, 1]);
+	}
+
+	#[test]
+	fn test_read_capture_file() {
+		const KEY_BOOKMARKS:u16 = 156;
+
+		let _hotkey_lock:MutexGuard<'_, ()> = HOTKEY_LOCK.lock().unwrap();
+		let capture_path:String = temp_path("evdev_capture");
+		fs::write(&capture_path, key_capture(&[(KEY_BOOKMARKS, 1)])).unwrap();
+		key_hook::read_events(fs::File::open(&capture_path).unwrap()).unwrap();
+		fs::remove_file(&capture_path).unwrap();
+		assert!(Key::new(keys::BROWSER_FAVORITES.key_code()).down());
+	}
+
+	#[test]
+	fn test_install_twice() {
+		const KEY_PAGEUP:u16 = 104;
+
+		let _hotkey_lock:MutexGuard<'_, ()> = HOTKEY_LOCK.lock().unwrap();
+		let _install_lock:MutexGuard<'_, ()> = INSTALL_LOCK.lock().unwrap_or_else(PoisonError::into_inner);
+		let capture_path:String = temp_path("install_twice");
+		fs::write(&capture_path, key_capture(&[(KEY_PAGEUP, 1), (KEY_PAGEUP, 0)])).unwrap();
+
+		let guard:HookGuard = key_hook::install_from_path(&capture_path).unwrap();
+		assert!(matches!(key_hook::install_from_path(&capture_path), Err(HookError::AlreadyInstalled)));
+		guard.uninstall();
+		key_hook::install_from_path(&capture_path).unwrap().uninstall();
+		fs::remove_file(&capture_path).unwrap();
+	}
+
+	#[test]
+	fn test_install_missing_path() {
+		let _hotkey_lock:MutexGuard<'_, ()> = HOTKEY_LOCK.lock().unwrap();
+		let _install_lock:MutexGuard<'_, ()> = INSTALL_LOCK.lock().unwrap_or_else(PoisonError::into_inner);
+		assert!(matches!(key_hook::install_from_path(temp_path("does_not_exist")), Err(HookError::Io(_))));
+		key_hook::install_from_path("/dev/null").unwrap().uninstall();
+	}
+
+	#[test]
+	fn test_uninstall_joins_listener() {
+		const KEY_PAGEDOWN:u16 = 109;
+
+		let _hotkey_lock:MutexGuard<'_, ()> = HOTKEY_LOCK.lock().unwrap();
+		let _install_lock:MutexGuard<'_, ()> = INSTALL_LOCK.lock().unwrap_or_else(PoisonError::into_inner);
+		let pipe_path:String = temp_path("pipe");
+		let pipe_path_c:CString = CString::new(pipe_path.clone()).unwrap();
+		assert_eq!(unsafe { libc::mkfifo(pipe_path_c.as_ptr(), 0o600) }, 0);
+
+		// Keep the pipe open, so the listener never reads the end of it.
+		let writer_path:String = pipe_path.clone();
+		let writer:thread::JoinHandle<fs::File> = thread::spawn(move || {
+			let mut pipe:fs::File = fs::OpenOptions::new().write(true).open(writer_path).unwrap();
+			pipe.write_all(&key_capture(&[(KEY_PAGEDOWN, 1)])).unwrap();
+			pipe
+		});
+		let guard:HookGuard = key_hook::install_from_path(&pipe_path).unwrap();
+		let pipe:fs::File = writer.join().unwrap();
+		assert!(wait_for(Duration::from_secs(2), || keys::NEXT.down()));
+
+		let uninstall_start:Instant = Instant::now();
+		guard.uninstall();
+		assert!(uninstall_start.elapsed() < Duration::from_secs(1));
+
+		drop(pipe);
+		fs::remove_file(&pipe_path).unwrap();
+	}
+
+	#[test]
+	fn test_subscribe_receives_timestamps() {
+		const KEY_CAPSLOCK:u16 = 58;
+
+		let _hotkey_lock:MutexGuard<'_, ()> = HOTKEY_LOCK.lock().unwrap();
+		let receiver:Receiver<InputEvent> = key_hook::subscribe();
+		let capture:Vec<u8> = raw_event_at(1_700_000_000, 250_000, EV_KEY, KEY_CAPSLOCK, 1);
+		key_hook::read_events(Cursor::new(capture)).unwrap();
+
+		let event:InputEvent = receiver.try_iter().find(|event| event.key_alteration().is_some_and(|(key, _)| key == keys::CAPITAL)).unwrap();
+		assert!(event.kind == InputEventKind::Key { key: keys::CAPITAL, down: true } && event.physical());
+		assert_eq!(event.timestamp.duration_since(UNIX_EPOCH).unwrap(), Duration::from_millis(1_700_000_000_250));
+	}
+
+	#[test]
+	fn test_subscribe_receives_motion() {
+		let _hotkey_lock:MutexGuard<'_, ()> = HOTKEY_LOCK.lock().unwrap();
+		let receiver:Receiver<InputEvent> = key_hook::subscribe();
+		let capture:Vec<u8> = [
+			raw_event(EV_REL, REL_X, 5), raw_event(EV_REL, REL_Y, -3), raw_event(EV_REL, REL_X, 2), raw_event(EV_REL, REL_WHEEL, -1), raw_event(EV_SYN, SYN_REPORT, 0),
+			raw_event(EV_ABS, ABS_X, 100), raw_event(EV_REL, REL_HWHEEL, 2), raw_event(EV_SYN, SYN_REPORT, 0)
+		].concat();
+		key_hook::read_events(Cursor::new(capture)).unwrap();
+
+		// Other tests only send key events.
+		let kinds:Vec<InputEventKind> = receiver.try_iter().map(|event| event.kind).filter(|kind| !matches!(kind, InputEventKind::Key { .. })).collect();
+		assert_eq!(kinds, vec![InputEventKind::MouseDisplace([7, -3]), InputEventKind::MouseWheel(-1), InputEventKind::MouseMove, InputEventKind::MouseHorizontalWheel(2)]);
+	}
+}
\ No newline at end of file
@@ -1,9 +1,44 @@
-#[cfg(test)]
+#[cfg(all(test, windows))]
 mod tests {
-	use crate::key_hook;
+	use crate::key_hook::{ self, HookError };
 
 	#[test]
 	fn test_install() {
-		key_hook::install();
+		let guard:key_hook::HookGuard = key_hook::install().unwrap();
+		assert!(matches!(key_hook::install(), Err(HookError::AlreadyInstalled)));
+		guard.uninstall();
+		key_hook::install().unwrap().uninstall();
 	}
 }
+
+#[cfg(test)]
+mod subscription_tests {
+	use crate::{ InputEvent, InputEventKind, key_hook, keys };
+	use std::{ sync::mpsc::Receiver, time::SystemTime };
+
+	#[test]
+	fn test_subscribe_receives_simulated_events() {
+		let receiver:Receiver<InputEvent> = key_hook::subscribe();
+		let start:SystemTime = SystemTime::now();
+		key_hook::simulate(keys::F24, true);
+		key_hook::simulate(keys::F24, false);
+
+		// Other tests simulate events too, only look at the key used here.
+		let events:Vec<InputEvent> = receiver.try_iter().filter(|event| event.key_alteration().is_some_and(|(key, _)| key == keys::F24)).collect();
+		assert_eq!(events.iter().map(|event| event.kind).collect::<Vec<InputEventKind>>(), vec![InputEventKind::Key { key: keys::F24, down: true }, InputEventKind::Key { key: keys::F24, down: false }]);
+		assert!(events.iter().all(|event| event.physical() && event.timestamp >= start));
+	}
+
+
+	#[cfg(feature="async")]
+	#[test]
+	fn test_event_stream() {
+		use futures::StreamExt;
+
+		let stream = key_hook::event_stream();
+		key_hook::simulate(keys::F10, true);
+		key_hook::simulate(keys::F10, false);
+		let events:Vec<InputEvent> = futures::executor::block_on(stream.filter(|event| std::future::ready(event.key_alteration().is_some_and(|(key, _)| key == keys::F10))).take(2).collect());
+		assert_eq!(events.iter().map(|event| event.kind).collect::<Vec<InputEventKind>>(), vec![InputEventKind::Key { key: keys::F10, down: true }, InputEventKind::Key { key: keys::F10, down: false }]);
+	}
+}
\ No newline at end of file
@@ -0,0 +1,114 @@
+use winapi::{ shared::{ minwindef::{ LPARAM, LRESULT, WPARAM }, windef::{ HHOOK, POINT } }, um::{ errhandlingapi::GetLastError, processthreadsapi::GetCurrentThreadId, winuser::{ CallNextHookEx, DispatchMessageW, GetCursorPos, GetMessageW, KBDLLHOOKSTRUCT, LLKHF_INJECTED, LLMHF_INJECTED, MSG, MSLLHOOKSTRUCT, PM_NOREMOVE, PeekMessageW, PostThreadMessageW, SetWindowsHookExW, TranslateMessage, UnhookWindowsHookEx, WHEEL_DELTA, WH_KEYBOARD_LL, WH_MOUSE_LL, WM_KEYDOWN, WM_KEYUP, WM_LBUTTONDOWN, WM_LBUTTONUP, WM_MBUTTONDOWN, WM_MBUTTONUP, WM_MOUSEHWHEEL, WM_MOUSEMOVE, WM_MOUSEWHEEL, WM_QUIT, WM_RBUTTONDOWN, WM_RBUTTONUP, WM_SYSKEYDOWN, WM_SYSKEYUP, WM_USER, WM_XBUTTONDOWN, WM_XBUTTONUP, XBUTTON1 } } };
+use std::{ mem, ptr::{ self, null_mut }, sync::mpsc::{ self, Receiver, Sender }, thread::{ self, JoinHandle }, time::SystemTime };
+use crate::{ InputEvent, InputEventKind, Key, keys, key_hook::{ HookError, handle_hook_event, has_subscribers } };
+
+
+
+/* HOOK INSTALLATION METHODS */
+
+/// Start a thread with the low-level mouse and keyboard hooks and a message loop. Returns a function that removes the hooks and joins the thread.
+pub(crate) fn start_hook() -> Result<Box<dyn FnOnce() + Send>, HookError> {
+	let (result_sender, result_receiver):(Sender<Result<u32, HookError>>, Receiver<Result<u32, HookError>>) = mpsc::channel();
+	let listener:JoinHandle<()> = thread::spawn(move || unsafe {
+
+		// Make sure the thread has a message queue before anything posts to it.
+		let mut message:MSG = mem::zeroed();
+		PeekMessageW(&mut message, null_mut(), WM_USER, WM_USER, PM_NOREMOVE);
+
+		// Create new hooks.
+		let hook_mouse:HHOOK = SetWindowsHookExW(WH_MOUSE_LL, Some(hook_callback), null_mut(), 0);
+		let hook_keyboard:HHOOK = SetWindowsHookExW(WH_KEYBOARD_LL, Some(hook_callback), null_mut(), 0);
+		if hook_mouse.is_null() || hook_keyboard.is_null() {
+			let error_code:u32 = GetLastError();
+			for hook in [hook_mouse, hook_keyboard].into_iter().filter(|hook| !hook.is_null()) {
+				UnhookWindowsHookEx(hook);
+			}
+			let _ = result_sender.send(Err(HookError::InstallFailed(format!("Failed to install mouse and keyboard hooks, error code {error_code}."))));
+			return;
+		}
+		let _ = result_sender.send(Ok(GetCurrentThreadId()));
+
+		// Run the message listener until a quit message is posted.
+		while GetMessageW(&mut message, null_mut(), 0, 0) > 0 {
+			TranslateMessage(&message);
+			DispatchMessageW(&message);
+		}
+		UnhookWindowsHookEx(hook_mouse);
+		UnhookWindowsHookEx(hook_keyboard);
+	});
+
+	// Wait for the hooks to be installed.
+	let thread_id:u32 = result_receiver.recv().map_err(|_| HookError::InstallFailed("The hook thread stopped unexpectedly.".to_string()))??;
+	Ok(Box::new(move || {
+		unsafe { PostThreadMessageW(thread_id, WM_QUIT, 0, 0); }
+		let _ = listener.join();
+	}))
+}
+
+
+
+/* HOOK HANDLING METHODS */
+
+/// The callback to catch the pressed keys.
+unsafe extern "system" fn hook_callback(key_code:i32, w_param:WPARAM, l_param:LPARAM) -> LRESULT {
+	let w_param:usize = w_param as usize;
+	let mut blocking:bool = false;
+
+	// Find key id and state change from arguments.
+	if key_code >= 0 {
+		if let Some(event) = params_to_input_event(w_param as u32, l_param) {
+			blocking = handle_hook_event(event);
+		}
+	}
+
+	// Move on to next callback.
+	if blocking {
+		1
+	} else {
+		unsafe { CallNextHookEx(ptr::null_mut(), key_code, w_param, l_param) }
+	}
+}
+
+/// Create an input event from hook callback arguments. Returns None if the arguments do not describe an input event. Mouse movement is only reported to subscribers.
+fn params_to_input_event(w_param:u32, l_param:LPARAM) -> Option<InputEvent> {
+
+	// Keyboard
+	if matches!(w_param, WM_KEYDOWN | WM_KEYUP | WM_SYSKEYDOWN | WM_SYSKEYUP) {
+		let kbd:&KBDLLHOOKSTRUCT = unsafe { &*(l_param as *const KBDLLHOOKSTRUCT) };
+		let mouse_position:[i32; 2] = if has_subscribers() { cursor_position() } else { [0, 0] };
+		return Some(InputEvent::key(Key::new(kbd.vkCode as u8), matches!(w_param, WM_KEYDOWN | WM_SYSKEYDOWN), kbd.flags & LLKHF_INJECTED != 0, SystemTime::now(), mouse_position));
+	}
+
+	// Mouse
+	else if (WM_MOUSEMOVE..=WM_MOUSEHWHEEL).contains(&w_param) {
+		let md:MSLLHOOKSTRUCT = unsafe { *(l_param as *const MSLLHOOKSTRUCT) };
+		let wheel_notches = || {
+			let delta:i32 = (md.mouseData >> 16) as i16 as i32;
+			if delta != 0 && delta.abs() < WHEEL_DELTA as i32 { delta.signum() } else { delta / WHEEL_DELTA as i32 } // High-resolution wheels scroll less than a notch at a time.
+		};
+		let kind:InputEventKind = match w_param {
+			WM_MOUSEMOVE if has_subscribers() => InputEventKind::MouseMove,
+			WM_LBUTTONDOWN => InputEventKind::Key { key: keys::LBUTTON, down: true },
+			WM_LBUTTONUP => InputEventKind::Key { key: keys::LBUTTON, down: false },
+			WM_RBUTTONDOWN => InputEventKind::Key { key: keys::RBUTTON, down: true },
+			WM_RBUTTONUP => InputEventKind::Key { key: keys::RBUTTON, down: false },
+			WM_MBUTTONDOWN => InputEventKind::Key { key: keys::MBUTTON, down: true },
+			WM_MBUTTONUP => InputEventKind::Key { key: keys::MBUTTON, down: false },
+			WM_XBUTTONDOWN | WM_XBUTTONUP => InputEventKind::Key { key: if (md.mouseData >> 16) as u16 == XBUTTON1 { keys::XBUTTON1 } else { keys::XBUTTON2 }, down: w_param == WM_XBUTTONDOWN },
+			WM_MOUSEWHEEL => InputEventKind::MouseWheel(wheel_notches()),
+			WM_MOUSEHWHEEL => InputEventKind::MouseHorizontalWheel(wheel_notches()),
+			_ => return None
+		};
+		return Some(InputEvent::new(kind, md.flags & LLMHF_INJECTED != 0, SystemTime::now(), [md.pt.x, md.pt.y]));
+	}
+
+	// No input event found.
+	None
+}
+
+/// Get the current position of the cursor.
+fn cursor_position() -> [i32; 2] {
+	let mut position:POINT = POINT { x: 0, y: 0 };
+	unsafe { GetCursorPos(&mut position); }
+	[position.x, position.y]
+}
\ No newline at end of file
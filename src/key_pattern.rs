@@ -1,10 +1,10 @@
 use std::{ time::Duration, ops::{ Add, BitAnd, BitAndAssign, BitOr, BitOrAssign, BitXor, BitXorAssign, Mul, Not, Shl, Shr } };
-use crate::{ InputBuilder, Key, key_hook::{PHYSICAL_KEY_STATES, VIRTUAL_KEY_STATES} };
+use crate::{ InputBuilder, InputExecution, Key, key_hook::{PHYSICAL_KEY_STATES, VIRTUAL_KEY_STATES} };
 use mini_rand::Randomizable;
 
 
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub struct KeyPattern {
 	high:u128,
 	low:u128
@@ -76,12 +76,12 @@ impl KeyPattern {
 
 	/// Returns self, filtered by the keys that are physically pressed.
 	pub fn pressed_pattern(&self) -> KeyPattern {
-		*self & unsafe { PHYSICAL_KEY_STATES }
+		*self & PHYSICAL_KEY_STATES.snapshot()
 	}
 
 	/// Returns self, filtered by the keys that are virtually pressed.
 	pub fn pressed_pattern_v(&self) -> KeyPattern {
-		*self & unsafe { VIRTUAL_KEY_STATES }
+		*self & VIRTUAL_KEY_STATES.snapshot()
 	}
 
 	/// Whether or not the pattern is completely physically pressed.
@@ -109,9 +109,9 @@ impl KeyPattern {
 		InputBuilder::new().with_send(self, duration.randomizable_value().as_millis() as u64).execute();
 	}
 
-	/// Send the key-pattern in a separate thread.
-	pub fn send_async<T>(&self, duration:T) where T:Randomizable<Duration> {
-		InputBuilder::new().with_send(self, duration.randomizable_value().as_millis() as u64).execute_async();
+	/// Send the key-pattern in a separate thread. The returned execution can be awaited.
+	pub fn send_async<T>(&self, duration:T) -> InputExecution where T:Randomizable<Duration> {
+		InputBuilder::new().with_send(self, duration.randomizable_value().as_millis() as u64).execute_async()
 	}
 }
 impl Default for KeyPattern {
@@ -0,0 +1,56 @@
+use std::sync::atomic::{ AtomicU64, Ordering };
+use crate::KeyPattern;
+
+
+
+/// A lock-free store of the state of all 256 key-codes. Can be read and written from any thread.
+pub(crate) struct KeyStateStore {
+	words:[AtomicU64; 4]
+}
+impl KeyStateStore {
+
+	/* CONSTRUCTOR METHODS */
+
+	/// Create a new store with all keys released.
+	pub(crate) const fn new() -> KeyStateStore {
+		KeyStateStore {
+			words: [AtomicU64::new(0), AtomicU64::new(0), AtomicU64::new(0), AtomicU64::new(0)]
+		}
+	}
+
+
+
+	/* USAGE METHODS */
+
+	/// Set the state of a key.
+	pub(crate) fn set(&self, key_code:u8, down:bool) {
+		if let Some((word_index, mask)) = KeyStateStore::location(key_code) {
+			if down {
+				self.words[word_index].fetch_or(mask, Ordering::SeqCst);
+			} else {
+				self.words[word_index].fetch_and(!mask, Ordering::SeqCst);
+			}
+		}
+	}
+
+	/// Get the state of a key.
+	pub(crate) fn get(&self, key_code:u8) -> bool {
+		KeyStateStore::location(key_code).map(|(word_index, mask)| self.words[word_index].load(Ordering::SeqCst) & mask != 0).unwrap_or(false)
+	}
+
+	/// Get a snapshot of the state of all keys as a pattern.
+	pub(crate) fn snapshot(&self) -> KeyPattern {
+		let words:[u128; 4] = [0, 1, 2, 3].map(|word_index| self.words[word_index].load(Ordering::SeqCst) as u128);
+		KeyPattern::new(words[2] | (words[3] << 64), words[0] | (words[1] << 64))
+	}
+
+	/// Get the index of the word and the bit-mask a key-code is stored at. Uses the same bit order as the patterns of keys.
+	fn location(key_code:u8) -> Option<(usize, u64)> {
+		if key_code == 0 {
+			None
+		} else {
+			let bit_index:usize = key_code as usize - 1;
+			Some((bit_index / 64, 1 << (bit_index % 64)))
+		}
+	}
+}
\ No newline at end of file
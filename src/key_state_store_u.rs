@@ -0,0 +1,53 @@
+#[cfg(test)]
+mod tests {
+	use crate::{ Key, KeyPattern, key_state_store::KeyStateStore };
+	use std::{ sync::Arc, thread };
+
+
+
+	#[test]
+	fn test_set_get() {
+		let store:KeyStateStore = KeyStateStore::new();
+		for key_code in 0..=255 {
+			assert!(!store.get(key_code));
+		}
+		store.set(65, true);
+		store.set(200, true);
+		assert!(store.get(65));
+		assert!(store.get(200));
+		assert!(!store.get(64));
+		store.set(65, false);
+		assert!(!store.get(65));
+		assert!(store.get(200));
+	}
+
+	#[test]
+	fn test_snapshot_matches_patterns() {
+		let store:KeyStateStore = KeyStateStore::new();
+		let key_codes:[u8; 8] = [1, 63, 64, 65, 128, 129, 192, 255];
+		for key_code in key_codes {
+			store.set(key_code, true);
+		}
+		let expected:KeyPattern = key_codes.iter().map(|key_code| Key::new(*key_code).pattern()).reduce(|a, b| a | b).unwrap();
+		assert_eq!(store.snapshot(), expected);
+	}
+
+	#[test]
+	fn test_concurrent_writes() {
+		let store:Arc<KeyStateStore> = Arc::new(KeyStateStore::new());
+		let threads:Vec<thread::JoinHandle<()>> = (0..8).map(|thread_index| {
+			let store:Arc<KeyStateStore> = store.clone();
+			thread::spawn(move || {
+				for repetition in 0..1000 {
+					for key_code in (1..=255).filter(|key_code| key_code % 8 == thread_index) {
+						store.set(key_code, repetition % 2 == 0);
+					}
+				}
+			})
+		}).collect();
+		for thread in threads {
+			thread.join().unwrap();
+		}
+		assert_eq!(store.snapshot(), KeyPattern::ZERO);
+	}
+}
\ No newline at end of file
@@ -0,0 +1,120 @@
+use std::sync::{ Mutex, MutexGuard };
+use crate::{ Hotkey, HotkeyHandle, Key, KeyPattern };
+
+
+
+static LAYER_STACK:Mutex<Vec<ActiveLayer>> = Mutex::new(Vec::new());
+
+
+
+struct ActiveLayer {
+	name:String,
+	one_shot_key:Option<KeyPattern> // Set for one-shot layers. The layer is deactivated by the next key press of any other key.
+}
+
+
+
+/// Activates a layer when pressed. The key is blocked, so other processes do not receive it.
+pub struct LayerKey {
+	key:Key,
+	layer:String,
+	mode:LayerKeyMode
+}
+enum LayerKeyMode { Momentary, Toggle, OneShot }
+impl LayerKey {
+
+	/* CONSTRUCTOR METHODS */
+
+	/// Create a key that activates the layer while it is held.
+	pub fn momentary(key:Key, layer:&str) -> LayerKey {
+		LayerKey { key, layer: layer.to_string(), mode: LayerKeyMode::Momentary }
+	}
+
+	/// Create a key that toggles the layer when pressed.
+	pub fn toggle(key:Key, layer:&str) -> LayerKey {
+		LayerKey { key, layer: layer.to_string(), mode: LayerKeyMode::Toggle }
+	}
+
+	/// Create a key that activates the layer until the next key is pressed.
+	pub fn one_shot(key:Key, layer:&str) -> LayerKey {
+		LayerKey { key, layer: layer.to_string(), mode: LayerKeyMode::OneShot }
+	}
+
+
+
+	/* REGISTERED STATIC METHODS */
+
+	/// Register the layer key. The returned handle can enable and disable the layer key like a hotkey.
+	pub fn register(self) -> HotkeyHandle {
+		let (key_pattern, press_layer, release_layer) = (self.key.pattern(), self.layer.clone(), self.layer);
+		let hotkey:Hotkey = Hotkey::new(&[self.key]).blocking().inline().on_release(|| {}); // The layer changes before the next key event is handled.
+		match self.mode {
+			LayerKeyMode::Momentary => hotkey.on_press(move || activate(&press_layer)).on_release(move || deactivate(&release_layer)),
+			LayerKeyMode::Toggle => hotkey.on_press(move || toggle(&press_layer)),
+			LayerKeyMode::OneShot => hotkey.on_press(move || activate_layer(&press_layer, Some(key_pattern)))
+		}.register()
+	}
+}
+
+
+
+/* LAYER STACK METHODS */
+
+/// Activate a layer, placing it on top of the layer stack. Hotkeys on higher layers take precedence over hotkeys with the same keys on lower layers.
+pub fn activate(layer:&str) {
+	activate_layer(layer, None);
+}
+
+/// Activate a layer until the next key is pressed.
+pub fn activate_one_shot(layer:&str) {
+	activate_layer(layer, Some(KeyPattern::ZERO));
+}
+
+/// Deactivate a layer, removing it from the layer stack.
+pub fn deactivate(layer:&str) {
+	LAYER_STACK.lock().unwrap().retain(|active_layer| active_layer.name != layer);
+}
+
+/// Activate the layer if it is not active, deactivate it otherwise.
+pub fn toggle(layer:&str) {
+	if is_active(layer) {
+		deactivate(layer);
+	} else {
+		activate(layer);
+	}
+}
+
+/// Whether or not the layer is active.
+pub fn is_active(layer:&str) -> bool {
+	LAYER_STACK.lock().unwrap().iter().any(|active_layer| active_layer.name == layer)
+}
+
+/// Get the names of all active layers, from the bottom of the stack to the top.
+pub fn active_layers() -> Vec<String> {
+	LAYER_STACK.lock().unwrap().iter().map(|active_layer| active_layer.name.clone()).collect()
+}
+
+/// Activate a layer, placing it on top of the layer stack. One-shot layers are deactivated when any other key than the given key is pressed.
+fn activate_layer(layer:&str, one_shot_key:Option<KeyPattern>) {
+	let mut stack:MutexGuard<'_, Vec<ActiveLayer>> = LAYER_STACK.lock().unwrap();
+	stack.retain(|active_layer| active_layer.name != layer);
+	stack.push(ActiveLayer { name: layer.to_string(), one_shot_key });
+}
+
+/// Get the one-shot layers a key press would deactivate.
+pub(crate) fn expiring_one_shot_layers(state_change_pattern:KeyPattern) -> Vec<String> {
+	LAYER_STACK.lock().unwrap().iter().filter(|active_layer| active_layer.one_shot_key.map(|key| key & state_change_pattern == KeyPattern::ZERO).unwrap_or(false)).map(|active_layer| active_layer.name.clone()).collect()
+}
+
+/// Deactivate the given one-shot layers.
+pub(crate) fn expire_one_shot_layers(layers:&[String]) {
+	LAYER_STACK.lock().unwrap().retain(|active_layer| active_layer.one_shot_key.is_none() || !layers.contains(&active_layer.name));
+}
+
+/// Get the rank of a layer in the stack. Hotkeys without a layer have rank 0, active layers have their position in the stack starting at 1 and inactive layers have no rank.
+pub(crate) fn layer_rank(layer:Option<&str>, stack:&[String]) -> Option<usize> {
+	match layer {
+		None => Some(0),
+		Some(layer) => stack.iter().position(|active_layer| active_layer == layer).map(|index| index + 1)
+	}
+}
\ No newline at end of file
@@ -0,0 +1,119 @@
+#[cfg(test)]
+mod tests {
+	use crate::{ Hotkey, HotkeyContext, LayerKey, hokey_u::tests::{ HOTKEY_LOCK, counter }, key_hook::simulate, keys, layers };
+	use std::sync::{ Arc, MutexGuard, atomic::{ AtomicBool, Ordering } };
+
+
+
+	#[test]
+	fn test_layer_stack() {
+		let _lock:MutexGuard<'_, ()> = HOTKEY_LOCK.lock().unwrap();
+		layers::activate("stack_a");
+		layers::activate("stack_b");
+		layers::activate("stack_a");
+		assert_eq!(layers::active_layers().into_iter().filter(|layer| layer.starts_with("stack_")).collect::<Vec<String>>(), vec!["stack_b", "stack_a"]);
+		layers::toggle("stack_b");
+		assert!(!layers::is_active("stack_b"));
+		layers::deactivate("stack_a");
+		assert!(!layers::is_active("stack_a"));
+	}
+
+	#[test]
+	fn test_momentary_layer() {
+		let _lock:MutexGuard<'_, ()> = HOTKEY_LOCK.lock().unwrap();
+		let (base_count, on_base) = counter();
+		let (layer_count, on_layer) = counter();
+		LayerKey::momentary(keys::NUMPAD7, "momentary").register();
+		Hotkey::new(&[keys::NUMPAD8]).on_press(on_base).inline().register();
+		Hotkey::new(&[keys::NUMPAD8]).layer("momentary").on_press(on_layer).inline().register();
+
+		simulate(keys::NUMPAD8, true);
+		simulate(keys::NUMPAD8, false);
+		assert!(simulate(keys::NUMPAD7, true));
+		assert!(layers::is_active("momentary"));
+		simulate(keys::NUMPAD8, true);
+		simulate(keys::NUMPAD8, false);
+		assert!(simulate(keys::NUMPAD7, false));
+		assert!(!layers::is_active("momentary"));
+		simulate(keys::NUMPAD8, true);
+		simulate(keys::NUMPAD8, false);
+		assert_eq!([base_count.load(Ordering::SeqCst), layer_count.load(Ordering::SeqCst)], [2, 1]);
+	}
+
+	#[test]
+	fn test_toggle_layer() {
+		let _lock:MutexGuard<'_, ()> = HOTKEY_LOCK.lock().unwrap();
+		let (layer_count, on_layer) = counter();
+		LayerKey::toggle(keys::NUMPAD9, "toggle").register();
+		Hotkey::new(&[keys::MULTIPLY]).layer("toggle").on_press(on_layer).inline().register();
+
+		for _ in 0..2 {
+			simulate(keys::NUMPAD9, true);
+			simulate(keys::NUMPAD9, false);
+			simulate(keys::MULTIPLY, true);
+			simulate(keys::MULTIPLY, false);
+		}
+		assert_eq!(layer_count.load(Ordering::SeqCst), 1);
+		assert!(!layers::is_active("toggle"));
+	}
+
+	#[test]
+	fn test_one_shot_layer() {
+		let _lock:MutexGuard<'_, ()> = HOTKEY_LOCK.lock().unwrap();
+		let (press_count, on_press) = counter();
+		let (release_count, on_release) = counter();
+		LayerKey::one_shot(keys::ADD, "one_shot").register();
+		Hotkey::new(&[keys::DIVIDE]).layer("one_shot").on_press(on_press).on_release(on_release).inline().register();
+
+		simulate(keys::ADD, true);
+		simulate(keys::ADD, true);
+		simulate(keys::ADD, false);
+		assert!(layers::is_active("one_shot"));
+		simulate(keys::DIVIDE, true);
+		assert!(!layers::is_active("one_shot"));
+		simulate(keys::DIVIDE, false);
+		simulate(keys::DIVIDE, true);
+		simulate(keys::DIVIDE, false);
+		assert_eq!([press_count.load(Ordering::SeqCst), release_count.load(Ordering::SeqCst)], [1, 1]);
+	}
+
+	#[test]
+	fn test_layer_predicate() {
+		let _lock:MutexGuard<'_, ()> = HOTKEY_LOCK.lock().unwrap();
+		let (base_count, on_base) = counter();
+		let (layer_count, on_layer) = counter();
+		let layer_allowed:Arc<AtomicBool> = Arc::new(AtomicBool::new(false));
+		let predicate_allowed:Arc<AtomicBool> = layer_allowed.clone();
+		Hotkey::new(&[keys::SUBTRACT]).on_press(on_base).inline().register();
+		Hotkey::new(&[keys::SUBTRACT]).layer("predicate").when(move |_| predicate_allowed.load(Ordering::SeqCst)).on_press(on_layer).inline().register();
+
+		// A layer hotkey its predicate does not allow does not take precedence.
+		layers::activate("predicate");
+		simulate(keys::SUBTRACT, true);
+		simulate(keys::SUBTRACT, false);
+		layer_allowed.store(true, Ordering::SeqCst);
+		simulate(keys::SUBTRACT, true);
+		simulate(keys::SUBTRACT, false);
+		layers::deactivate("predicate");
+		assert_eq!([base_count.load(Ordering::SeqCst), layer_count.load(Ordering::SeqCst)], [1, 1]);
+	}
+
+	#[test]
+	fn test_context_active_layer() {
+		let _lock:MutexGuard<'_, ()> = HOTKEY_LOCK.lock().unwrap();
+		let (press_count, on_press) = counter();
+		Hotkey::new(&[keys::NUMLOCK]).when(|context:&HotkeyContext| context.active_layer() == Some("context_top")).on_press(on_press).inline().register();
+
+		// Only the layer on top of the stack is the active layer.
+		layers::activate("context_bottom");
+		layers::activate("context_top");
+		simulate(keys::NUMLOCK, true);
+		simulate(keys::NUMLOCK, false);
+		layers::activate("context_bottom");
+		simulate(keys::NUMLOCK, true);
+		simulate(keys::NUMLOCK, false);
+		layers::deactivate("context_bottom");
+		layers::deactivate("context_top");
+		assert_eq!(press_count.load(Ordering::SeqCst), 1);
+	}
+}
\ No newline at end of file
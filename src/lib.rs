@@ -1,18 +1,92 @@
 mod key_pattern;
 mod key_pattern_u;
 mod key_hook_u;
+#[cfg(windows)]
+mod key_hook_windows;
+#[cfg(target_os="linux")]
+mod key_hook_evdev;
+mod key_hook_evdev_u;
 mod key;
 mod hokey;
+mod hokey_u;
+mod handler_executor;
+mod handler_executor_u;
+mod hotkey_context;
+mod hotkey_event;
+mod hotkey_gestures;
+mod hotkey_group;
+mod hotkey_group_u;
+mod hotkeys_u;
+mod hotkey_registry;
+mod hotkey_sequence;
+mod hotkey_timer;
+mod match_mode;
+mod match_mode_u;
+mod key_state_store;
+mod key_state_store_u;
 mod sleep;
 mod sleep_u;
 mod input_builder;
+mod input_builder_u;
+mod input_execution;
+mod input_backend;
+mod remap;
+mod mod_tap;
+mod mod_tap_u;
+mod hotstring;
+mod hotstring_u;
+mod layers_u;
+mod remap_u;
+mod input_event;
+mod window_source;
+#[cfg(all(target_os="linux", feature="x11"))]
+mod window_source_x11;
+mod window_source_x11_u;
+mod window_matcher;
+mod window_matcher_u;
+#[cfg(windows)]
+mod input_backend_windows;
+mod input_backend_recording;
+#[cfg(all(target_os="linux", feature="x11"))]
+mod input_backend_x11;
+mod input_backend_x11_u;
+#[cfg(all(target_os="linux", feature="uinput"))]
+mod input_backend_uinput;
+mod input_backend_uinput_u;
+#[cfg(target_os="linux")]
+mod evdev_codes;
 
+pub mod hotkeys;
 pub mod key_hook;
 pub mod keys;
+pub mod layers;
 pub mod mouse;
 
 pub use key::Key;
 pub use key_pattern::KeyPattern;
-pub use hokey::{ Hotkey, HotkeyHandle };
+pub use hokey::{ Hotkey, HotkeyGuard, HotkeyHandle };
+pub use hotkey_group::{ HotkeyGroup, HotkeyGroupHandle };
+pub use handler_executor::{ HandlerExecutor, InlineExecutor, WorkerPool, set_handler_executor, handler_executor };
+pub use hotkey_event::{ HotkeyEvent, Propagation };
+pub use hotkey_context::{ HotkeyContext, set_app_state, clear_app_state };
+pub use match_mode::MatchMode;
+pub use remap::Remap;
+pub use mod_tap::{ ModTap, ModTapHandle };
+pub use hotstring::Hotstring;
+pub use layers::LayerKey;
 pub use sleep::*;
-pub use input_builder::*;
\ No newline at end of file
+pub use input_builder::*;
+pub use input_execution::InputExecution;
+pub use input_backend::*;
+pub use input_event::{ InputEvent, InputEventKind };
+pub use window_source::*;
+#[cfg(all(target_os="linux", feature="x11"))]
+pub use window_source_x11::X11WindowSource;
+pub use window_matcher::WindowMatcher;
+#[cfg(windows)]
+pub use input_backend_windows::WindowsBackend;
+pub use input_backend_recording::{ RecordedAction, RecordingBackend };
+#[cfg(all(target_os="linux", feature="x11"))]
+pub use input_backend_x11::X11Backend;
+#[cfg(all(target_os="linux", feature="uinput"))]
+pub use input_backend_uinput::UinputBackend;
\ No newline at end of file
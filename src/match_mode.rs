@@ -0,0 +1,45 @@
+use crate::{ Key, KeyPattern, keys };
+
+
+
+const MODIFIER_FAMILIES:&[&[Key]] = &[
+	&[keys::SHIFT, keys::LSHIFT, keys::RSHIFT],
+	&[keys::CONTROL, keys::LCONTROL, keys::RCONTROL],
+	&[keys::ALT, keys::LALT, keys::RALT],
+	&[keys::LWIN, keys::RWIN]
+];
+
+
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum MatchMode {
+	/// Matches when all keys are held, regardless of other held keys.
+	#[default]
+	Superset,
+	/// Matches when all keys are held and no other modifiers are held. Other held keys are ignored.
+	IgnoreNonModifiers,
+	/// Matches when all keys are held and no other keys are held.
+	Exact
+}
+impl MatchMode {
+
+	/// Whether or not the held keys match the keys of a hotkey. Left and right variants of a modifier count as the same modifier.
+	pub fn matches(&self, key_pattern:KeyPattern, active_pattern:&KeyPattern) -> bool {
+		if key_pattern & *active_pattern != key_pattern {
+			return false;
+		}
+		if *self == MatchMode::Superset {
+			return true;
+		}
+		let extra_keys:Vec<Key> = (*active_pattern & !key_pattern).keys();
+		match self {
+			MatchMode::IgnoreNonModifiers => extra_keys.iter().filter(|key| key.is_modifier_key()).all(|key| MatchMode::modifier_in_pattern(key, key_pattern)),
+			_ => extra_keys.iter().all(|key| key.is_modifier_key() && MatchMode::modifier_in_pattern(key, key_pattern))
+		}
+	}
+
+	/// Whether or not the pattern contains the modifier or another variant of it.
+	fn modifier_in_pattern(modifier:&Key, key_pattern:KeyPattern) -> bool {
+		MODIFIER_FAMILIES.iter().find(|family| family.contains(modifier)).map(|family| family.iter().any(|key| key.pattern() & key_pattern != KeyPattern::ZERO)).unwrap_or(false)
+	}
+}
\ No newline at end of file
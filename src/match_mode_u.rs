@@ -0,0 +1,46 @@
+#[cfg(test)]
+mod tests {
+	use crate::{ Key, KeyPattern, MatchMode, keys };
+
+
+
+	/// Create a pattern from a list of keys.
+	fn pattern(keys:&[Key]) -> KeyPattern {
+		keys.iter().fold(KeyPattern::ZERO, |pattern, key| pattern | key.pattern())
+	}
+
+
+
+	#[test]
+	fn test_missing_keys() {
+		for mode in [MatchMode::Superset, MatchMode::IgnoreNonModifiers, MatchMode::Exact] {
+			assert!(!mode.matches(pattern(&[keys::LCONTROL, keys::C]), &pattern(&[keys::C])));
+		}
+	}
+
+	#[test]
+	fn test_superset() {
+		let hotkey:KeyPattern = pattern(&[keys::LCONTROL, keys::C]);
+		assert!(MatchMode::Superset.matches(hotkey, &pattern(&[keys::LCONTROL, keys::C])));
+		assert!(MatchMode::Superset.matches(hotkey, &pattern(&[keys::LCONTROL, keys::LSHIFT, keys::C])));
+		assert!(MatchMode::Superset.matches(hotkey, &pattern(&[keys::LCONTROL, keys::C, keys::X])));
+	}
+
+	#[test]
+	fn test_ignore_non_modifiers() {
+		let hotkey:KeyPattern = pattern(&[keys::LCONTROL, keys::C]);
+		assert!(MatchMode::IgnoreNonModifiers.matches(hotkey, &pattern(&[keys::LCONTROL, keys::C])));
+		assert!(MatchMode::IgnoreNonModifiers.matches(hotkey, &pattern(&[keys::LCONTROL, keys::C, keys::X])));
+		assert!(!MatchMode::IgnoreNonModifiers.matches(hotkey, &pattern(&[keys::LCONTROL, keys::LSHIFT, keys::C])));
+		assert!(!MatchMode::IgnoreNonModifiers.matches(hotkey, &pattern(&[keys::LCONTROL, keys::LWIN, keys::C])));
+	}
+
+	#[test]
+	fn test_exact() {
+		let hotkey:KeyPattern = pattern(&[keys::CONTROL, keys::C]);
+		assert!(MatchMode::Exact.matches(hotkey, &pattern(&[keys::CONTROL, keys::C])));
+		assert!(MatchMode::Exact.matches(hotkey, &pattern(&[keys::CONTROL, keys::LCONTROL, keys::C])));
+		assert!(!MatchMode::Exact.matches(hotkey, &pattern(&[keys::CONTROL, keys::C, keys::X])));
+		assert!(!MatchMode::Exact.matches(hotkey, &pattern(&[keys::CONTROL, keys::RALT, keys::C])));
+	}
+}
\ No newline at end of file
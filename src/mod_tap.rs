@@ -0,0 +1,202 @@
+use std::{ mem, sync::{ Mutex, MutexGuard, atomic::{ AtomicU64, Ordering } }, time::{ Duration, Instant } };
+use crate::{ InputBuilder, Key, hotkey_timer::HOTKEY_TIMER };
+
+
+
+static REGISTERED_MOD_TAPS:Mutex<Vec<ModTap>> = Mutex::new(Vec::new());
+const DEFAULT_TAPPING_TERM:Duration = Duration::from_millis(200);
+type BufferedEvent = (u8, bool, Instant); // The key-code, whether the key was pressed and the time of the event.
+
+
+
+#[derive(Clone, Copy, PartialEq)]
+enum ModTapPhase { Released, Undecided, Held }
+
+
+
+/// A key that acts as a modifier when held and as itself when tapped. While it is undecided, other keys are blocked and buffered, then sent again once it is decided. Keys of other mod-taps are buffered too, and act as mod-taps once sent again, so multiple mod-taps can be held at once.
+/// Only the Windows hook can block keys, on Linux both the physical keys and the sent keys are received. Sent keys do not trigger hotkeys.
+pub struct ModTap {
+	id:u64,
+	key:Key,
+	modifier:Key,
+	tapping_term:Duration,
+	permissive_hold:bool,
+
+	phase:ModTapPhase,
+	generation:u64,
+	buffer:Vec<BufferedEvent>
+}
+impl ModTap {
+
+	/* CONSTRUCTOR METHODS */
+
+	/// Create a new mod-tap that acts as the modifier when held and as the key when tapped.
+	pub fn new(key:Key, modifier:Key) -> ModTap {
+		static ID_GENERATOR:AtomicU64 = AtomicU64::new(0);
+		ModTap {
+			id: ID_GENERATOR.fetch_add(1, Ordering::SeqCst) + 1,
+			key,
+			modifier,
+			tapping_term: DEFAULT_TAPPING_TERM,
+			permissive_hold: false,
+
+			phase: ModTapPhase::Released,
+			generation: 0,
+			buffer: Vec::new()
+		}
+	}
+
+	/// Return self with a different tapping term. Holding the key longer than the tapping term makes it act as the modifier.
+	pub fn tapping_term(mut self, tapping_term:Duration) -> Self {
+		self.tapping_term = tapping_term;
+		self
+	}
+
+	/// Return self with permissive hold enabled. Pressing and releasing another key while the key is held makes it act as the modifier, even within the tapping term.
+	/// Without permissive hold, releasing the key within the tapping term always makes it act as the key, so rolling over keys while typing sends all keys in order.
+	pub fn permissive_hold(mut self) -> Self {
+		self.permissive_hold = true;
+		self
+	}
+
+
+
+	/* REGISTERED STATIC METHODS */
+
+	/// Register the mod-tap to the static list. The returned handle can unregister the mod-tap.
+	pub fn register(self) -> ModTapHandle {
+		let handle:ModTapHandle = ModTapHandle(self.id);
+		REGISTERED_MOD_TAPS.lock().unwrap().push(self);
+		handle
+	}
+
+
+
+	/* USAGE METHODS */
+
+	/// Update the mod-tap with a key event that happened at the given time, adding the inputs to send. Returns whether the event is blocked and the buffered events to handle again, or None if the event does not concern the mod-tap.
+	fn update(&mut self, key_code:u8, down:bool, time:Instant, inputs:&mut InputBuilder) -> Option<(bool, Vec<BufferedEvent>)> {
+
+		// Events of the key itself.
+		if key_code == self.key.key_code() {
+			return match (self.phase, down) {
+				(ModTapPhase::Released, true) => {
+					self.phase = ModTapPhase::Undecided;
+					self.generation += 1;
+					let (id, generation):(u64, u64) = (self.id, self.generation);
+					HOTKEY_TIMER.schedule(time + self.tapping_term, move || tapping_term_expired(id, generation));
+					Some((true, Vec::new()))
+				},
+				(ModTapPhase::Undecided, false) => {
+					self.phase = ModTapPhase::Released;
+					inputs.add_press(&self.key);
+					inputs.add_release(&self.key);
+					Some((true, mem::take(&mut self.buffer)))
+				},
+				(ModTapPhase::Held, false) => {
+					self.phase = ModTapPhase::Released;
+					inputs.add_release(&self.modifier);
+					Some((true, Vec::new()))
+				},
+				(ModTapPhase::Released, false) => None,
+				(_, true) => Some((true, Vec::new())) // Repeats
+			};
+		}
+
+		// While undecided, other keys are buffered. Keys that were pressed before the mod-tap are released normally.
+		if self.phase != ModTapPhase::Undecided || !down && !self.buffer.iter().any(|(buffered_key_code, buffered_down, _)| *buffered_key_code == key_code && *buffered_down) {
+			return None;
+		}
+		self.buffer.push((key_code, down, time));
+		if self.permissive_hold && !down {
+			return Some((true, self.hold(inputs)));
+		}
+		Some((true, Vec::new()))
+	}
+
+	/// Decide the mod-tap is held, adding the inputs to send. Returns the buffered events to handle again.
+	fn hold(&mut self, inputs:&mut InputBuilder) -> Vec<BufferedEvent> {
+		self.phase = ModTapPhase::Held;
+		inputs.add_press(&self.modifier);
+		mem::take(&mut self.buffer)
+	}
+}
+
+
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ModTapHandle(u64);
+impl ModTapHandle {
+
+	/// Remove the mod-tap from the static list. A held modifier is released. An undecided key is pressed, followed by the keys it buffered, as the key is no longer a mod-tap when it is released.
+	pub fn unregister(self) {
+		let mut mod_taps:MutexGuard<'_, Vec<ModTap>> = REGISTERED_MOD_TAPS.lock().unwrap();
+		let mut inputs:InputBuilder = InputBuilder::new();
+		if let Some(index) = mod_taps.iter().position(|mod_tap| mod_tap.id == self.0) {
+			let mut mod_tap:ModTap = mod_taps.remove(index);
+			match mod_tap.phase {
+				ModTapPhase::Released => {},
+				ModTapPhase::Undecided => {
+					inputs.add_press(&mod_tap.key);
+					replay(&mut mod_taps, mem::take(&mut mod_tap.buffer), &mut inputs);
+				},
+				ModTapPhase::Held => inputs.add_release(&mod_tap.modifier)
+			}
+		}
+		drop(mod_taps);
+		inputs.execute();
+	}
+}
+
+
+
+/// Handle a physical key event for all registered mod-taps. Returns whether the event is blocked, or None if no mod-tap handled it.
+pub(crate) fn handle_mod_taps(key_code:u8, down:bool) -> Option<bool> {
+	let mut mod_taps:MutexGuard<'_, Vec<ModTap>> = REGISTERED_MOD_TAPS.lock().unwrap();
+	let mut inputs:InputBuilder = InputBuilder::new();
+	let blocking:bool = handle_event(&mut mod_taps, key_code, down, Instant::now(), &mut inputs)?;
+	drop(mod_taps);
+	inputs.execute();
+	Some(blocking)
+}
+
+/// Handle a key event for the given mod-taps, adding the inputs to send. Undecided mod-taps handle events first, so they also buffer the keys of other mod-taps.
+/// Buffered events are handled again once the mod-tap is decided, so the keys of other mod-taps act as mod-taps too. Returns whether the event is blocked, or None if no mod-tap handled it.
+fn handle_event(mod_taps:&mut [ModTap], key_code:u8, down:bool, time:Instant, inputs:&mut InputBuilder) -> Option<bool> {
+	let mut result:Option<(bool, Vec<BufferedEvent>)> = None;
+	for undecided in [true, false] {
+		result = mod_taps.iter_mut().filter(|mod_tap| (mod_tap.phase == ModTapPhase::Undecided) == undecided).find_map(|mod_tap| mod_tap.update(key_code, down, time, inputs));
+		if result.is_some() {
+			break;
+		}
+	}
+	let (blocking, buffered_events) = result?;
+	replay(mod_taps, buffered_events, inputs);
+	Some(blocking)
+}
+
+/// Handle buffered events again. Events no mod-tap handles are added to the inputs.
+fn replay(mod_taps:&mut [ModTap], buffered_events:Vec<BufferedEvent>, inputs:&mut InputBuilder) {
+	for (key_code, down, time) in buffered_events {
+		if handle_event(mod_taps, key_code, down, time, inputs).is_none() {
+			if down {
+				inputs.add_press(&Key::new(key_code));
+			} else {
+				inputs.add_release(&Key::new(key_code));
+			}
+		}
+	}
+}
+
+/// Decide the mod-tap is held when it is still undecided after the tapping term.
+fn tapping_term_expired(id:u64, generation:u64) {
+	let mut mod_taps:MutexGuard<'_, Vec<ModTap>> = REGISTERED_MOD_TAPS.lock().unwrap();
+	let mut inputs:InputBuilder = InputBuilder::new();
+	if let Some(mod_tap) = mod_taps.iter_mut().find(|mod_tap| mod_tap.id == id && mod_tap.phase == ModTapPhase::Undecided && mod_tap.generation == generation) {
+		let buffered_events:Vec<BufferedEvent> = mod_tap.hold(&mut inputs);
+		replay(&mut mod_taps, buffered_events, &mut inputs);
+	}
+	drop(mod_taps);
+	inputs.execute();
+}
\ No newline at end of file
@@ -0,0 +1,148 @@
+#[cfg(test)]
+mod tests {
+	use crate::{ Key, ModTap, ModTapHandle, RecordingBackend, hokey_u::tests::HOTKEY_LOCK, input_builder_u::tests::{ GLOBAL_BACKEND_LOCK, global_recorder }, key_hook::simulate, keys };
+	use std::{ sync::MutexGuard, thread, time::{ Duration, Instant } };
+
+
+
+	/// Get all recorded key actions of the given keys as a list of keys and states.
+	fn recorded_keys(recorder:&RecordingBackend, keys:&[Key]) -> Vec<(Key, bool)> {
+		recorder.actions().into_iter().filter_map(|recorded| recorded.action.key_alteration()).filter(|(key_code, _)| keys.iter().any(|key| key.key_code() == *key_code)).map(|(key_code, down)| (Key::new(key_code), down)).collect()
+	}
+
+
+
+	#[test]
+	fn test_tap() {
+		let _hotkey_lock:MutexGuard<'_, ()> = HOTKEY_LOCK.lock().unwrap();
+		let _backend_lock:MutexGuard<'_, ()> = GLOBAL_BACKEND_LOCK.lock().unwrap();
+		let recorder:RecordingBackend = global_recorder();
+		let handle:ModTapHandle = ModTap::new(keys::Q, keys::LCONTROL).register();
+
+		assert!(simulate(keys::Q, true));
+		assert!(simulate(keys::Q, true));
+		assert!(simulate(keys::Q, false));
+		assert_eq!(recorded_keys(&recorder, &[keys::Q, keys::LCONTROL]), vec![(keys::Q, true), (keys::Q, false)]);
+		handle.unregister();
+	}
+
+	#[test]
+	fn test_hold_after_tapping_term() {
+		let _hotkey_lock:MutexGuard<'_, ()> = HOTKEY_LOCK.lock().unwrap();
+		let _backend_lock:MutexGuard<'_, ()> = GLOBAL_BACKEND_LOCK.lock().unwrap();
+		let recorder:RecordingBackend = global_recorder();
+		let handle:ModTapHandle = ModTap::new(keys::W, keys::LCONTROL).tapping_term(Duration::from_millis(30)).register();
+
+		simulate(keys::W, true);
+		simulate(keys::T, true);
+		let start:Instant = Instant::now();
+		while recorded_keys(&recorder, &[keys::LCONTROL]).is_empty() && start.elapsed() < Duration::from_secs(2) {
+			thread::sleep(Duration::from_millis(5));
+		}
+		assert!(!simulate(keys::T, false));
+		simulate(keys::W, false);
+		assert_eq!(recorded_keys(&recorder, &[keys::W, keys::T, keys::LCONTROL]), vec![(keys::LCONTROL, true), (keys::T, true), (keys::LCONTROL, false)]);
+		handle.unregister();
+	}
+
+	#[test]
+	fn test_permissive_hold() {
+		let _hotkey_lock:MutexGuard<'_, ()> = HOTKEY_LOCK.lock().unwrap();
+		let _backend_lock:MutexGuard<'_, ()> = GLOBAL_BACKEND_LOCK.lock().unwrap();
+		let recorder:RecordingBackend = global_recorder();
+		let handle:ModTapHandle = ModTap::new(keys::E, keys::LCONTROL).tapping_term(Duration::from_secs(10)).permissive_hold().register();
+
+		simulate(keys::E, true);
+		assert!(simulate(keys::Y, true));
+		assert!(simulate(keys::Y, false));
+		simulate(keys::E, false);
+		assert_eq!(recorded_keys(&recorder, &[keys::E, keys::Y, keys::LCONTROL]), vec![(keys::LCONTROL, true), (keys::Y, true), (keys::Y, false), (keys::LCONTROL, false)]);
+		handle.unregister();
+	}
+
+	#[test]
+	fn test_rolling_without_permissive_hold() {
+		let _hotkey_lock:MutexGuard<'_, ()> = HOTKEY_LOCK.lock().unwrap();
+		let _backend_lock:MutexGuard<'_, ()> = GLOBAL_BACKEND_LOCK.lock().unwrap();
+		let recorder:RecordingBackend = global_recorder();
+		let handle:ModTapHandle = ModTap::new(keys::R, keys::LCONTROL).tapping_term(Duration::from_secs(10)).register();
+
+		// A key pressed before the mod-tap is released normally.
+		simulate(keys::I, true);
+		simulate(keys::R, true);
+		assert!(!simulate(keys::I, false));
+
+		// Keys pressed while the mod-tap is undecided are sent after its tap.
+		assert!(simulate(keys::U, true));
+		assert!(simulate(keys::U, false));
+		simulate(keys::R, false);
+		assert_eq!(recorded_keys(&recorder, &[keys::R, keys::U, keys::I, keys::LCONTROL]), vec![(keys::R, true), (keys::R, false), (keys::U, true), (keys::U, false)]);
+		handle.unregister();
+	}
+
+	#[test]
+	fn test_two_mod_taps_held() {
+		let _hotkey_lock:MutexGuard<'_, ()> = HOTKEY_LOCK.lock().unwrap();
+		let _backend_lock:MutexGuard<'_, ()> = GLOBAL_BACKEND_LOCK.lock().unwrap();
+		let recorder:RecordingBackend = global_recorder();
+		let handles:[ModTapHandle; 2] = [ModTap::new(keys::D, keys::LCONTROL).tapping_term(Duration::from_millis(30)).register(), ModTap::new(keys::O, keys::LSHIFT).tapping_term(Duration::from_millis(30)).register()];
+
+		// The second mod-tap is buffered by the first, then held too.
+		simulate(keys::D, true);
+		simulate(keys::O, true);
+		let start:Instant = Instant::now();
+		while recorded_keys(&recorder, &[keys::LSHIFT]).is_empty() && start.elapsed() < Duration::from_secs(2) {
+			thread::sleep(Duration::from_millis(5));
+		}
+		assert!(!simulate(keys::M, true));
+		assert!(!simulate(keys::M, false));
+		simulate(keys::O, false);
+		simulate(keys::D, false);
+		assert_eq!(recorded_keys(&recorder, &[keys::D, keys::O, keys::LCONTROL, keys::LSHIFT]), vec![(keys::LCONTROL, true), (keys::LSHIFT, true), (keys::LSHIFT, false), (keys::LCONTROL, false)]);
+		for handle in handles {
+			handle.unregister();
+		}
+	}
+
+	#[test]
+	fn test_two_mod_taps_rolling() {
+		let _hotkey_lock:MutexGuard<'_, ()> = HOTKEY_LOCK.lock().unwrap();
+		let _backend_lock:MutexGuard<'_, ()> = GLOBAL_BACKEND_LOCK.lock().unwrap();
+		let recorder:RecordingBackend = global_recorder();
+		let handles:[ModTapHandle; 2] = [ModTap::new(keys::KEY_2, keys::LCONTROL).tapping_term(Duration::from_secs(10)).register(), ModTap::new(keys::KEY_3, keys::LSHIFT).tapping_term(Duration::from_secs(10)).permissive_hold().register()];
+
+		// Rolling over both keys taps both in order.
+		simulate(keys::KEY_2, true);
+		simulate(keys::KEY_3, true);
+		simulate(keys::KEY_2, false);
+		simulate(keys::KEY_3, false);
+		assert_eq!(recorded_keys(&recorder, &[keys::KEY_2, keys::KEY_3, keys::LCONTROL, keys::LSHIFT]), vec![(keys::KEY_2, true), (keys::KEY_2, false), (keys::KEY_3, true), (keys::KEY_3, false)]);
+
+		// Tapping the first key within the second makes the second a modifier with permissive hold.
+		let recorder:RecordingBackend = global_recorder();
+		simulate(keys::KEY_3, true);
+		simulate(keys::KEY_2, true);
+		simulate(keys::KEY_2, false);
+		simulate(keys::KEY_3, false);
+		assert_eq!(recorded_keys(&recorder, &[keys::KEY_2, keys::KEY_3, keys::LCONTROL, keys::LSHIFT]), vec![(keys::LSHIFT, true), (keys::KEY_2, true), (keys::KEY_2, false), (keys::LSHIFT, false)]);
+		for handle in handles {
+			handle.unregister();
+		}
+	}
+
+	#[test]
+	fn test_unregister() {
+		let _hotkey_lock:MutexGuard<'_, ()> = HOTKEY_LOCK.lock().unwrap();
+		let _backend_lock:MutexGuard<'_, ()> = GLOBAL_BACKEND_LOCK.lock().unwrap();
+		let recorder:RecordingBackend = global_recorder();
+		let handle:ModTapHandle = ModTap::new(keys::Q, keys::LCONTROL).tapping_term(Duration::from_secs(10)).register();
+
+		// Unregistering an undecided mod-tap presses its key, releasing it is no longer handled.
+		assert!(simulate(keys::Q, true));
+		assert!(simulate(keys::U, true));
+		handle.unregister();
+		assert!(!simulate(keys::U, false));
+		assert!(!simulate(keys::Q, false));
+		assert_eq!(recorded_keys(&recorder, &[keys::Q, keys::U, keys::LCONTROL]), vec![(keys::Q, true), (keys::U, true)]);
+	}
+}
\ No newline at end of file
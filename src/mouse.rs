@@ -1,5 +1,4 @@
-use winapi::{ shared::windef::POINT, um::winuser::GetCursorPos };
-use crate::{ InputBuilder, keys::LBUTTON };
+use crate::{ InputBuilder, input_backend, keys::LBUTTON };
 use mini_rand::Randomizable;
 use std::time::Duration;
 
@@ -17,9 +16,12 @@ pub fn move_to(position:[i32; 2]) {
 
 /// Get the current position of the mouse, relative to the screen.
 pub fn get_pos() -> [i32; 2] {	
-	let mut cursor_pos:POINT = POINT { x: 0, y: 0 };
-	unsafe { GetCursorPos(&mut cursor_pos); }
-	[cursor_pos.x, cursor_pos.y]
+	input_backend().mouse_position()
+}
+
+/// Scroll the mouse wheel a number of notches. Positive values scroll away from the user.
+pub fn scroll(delta:i32) {
+	InputBuilder::new().with_mouse_wheel(delta).execute();
 }
 
 /// Click at a specific location without moving the cursor.
@@ -0,0 +1,96 @@
+use crate::{ Hotkey, HotkeyHandle, InputBuilder, Key, KeyPattern };
+use std::sync::{ Arc, atomic::{ AtomicBool, Ordering } };
+
+
+
+enum RemapTarget {
+	Pattern(KeyPattern),
+	Input(InputBuilder)
+}
+
+
+
+/// Replaces a physical key with other input. The physical key is blocked, so other processes only receive the replacement.
+/// Only the Windows hook can block keys, on Linux both the physical key and the replacement are received.
+pub struct Remap {
+	source:Key,
+	target:RemapTarget,
+	layer:Option<String>
+}
+impl Remap {
+
+	/* CONSTRUCTOR METHODS */
+
+	/// Create a remap that replaces a key with another key. The target key is pressed, repeated and released together with the source key.
+	pub fn key(source:Key, target:Key) -> Remap {
+		Remap::pattern(source, target.pattern())
+	}
+
+	/// Create a remap that replaces a key with a key-pattern. All keys in the pattern are pressed, repeated and released together with the source key. Modifiers in the pattern are pressed first and released last.
+	pub fn pattern(source:Key, target:KeyPattern) -> Remap {
+		Remap {
+			source,
+			target: RemapTarget::Pattern(target),
+			layer: None
+		}
+	}
+
+	/// Create a remap that sends inputs when the key is pressed. The inputs are sent in a separate thread and repeats are ignored.
+	pub fn input(source:Key, target:InputBuilder) -> Remap {
+		Remap {
+			source,
+			target: RemapTarget::Input(target),
+			layer: None
+		}
+	}
+
+	/// Return self assigned to a layer. The remap only applies while the layer is active.
+	pub fn layer(mut self, layer:&str) -> Self {
+		self.layer = Some(layer.to_string());
+		self
+	}
+
+
+
+	/* REGISTERED STATIC METHODS */
+
+	/// Register the remap. The returned handle can enable and disable the remap like a hotkey.
+	pub fn register(self) -> HotkeyHandle {
+		let mut hotkey:Hotkey = Hotkey::new(&[self.source]).blocking().inline(); // The replacement is sent in order with the blocked key events.
+		if let Some(layer) = &self.layer {
+			hotkey = hotkey.layer(layer);
+		}
+		match self.target {
+			RemapTarget::Pattern(target) => {
+
+				// Modifiers are pressed before and released after the other keys, so the other keys are modified.
+				let modifiers:KeyPattern = target.keys().into_iter().filter(Key::is_modifier_key).fold(KeyPattern::ZERO, |modifiers, key| modifiers | key);
+				let press_inputs:InputBuilder = InputBuilder::new().with_press(&modifiers).with_press(&(target & !modifiers));
+				let release_inputs:InputBuilder = InputBuilder::new().with_release(&(target & !modifiers)).with_release(&modifiers);
+				let repeat_inputs:InputBuilder = press_inputs.clone();
+
+				// Only release the target when this remap pressed it, so enabling the remap while the source key is held does not release keys the user holds.
+				let target_pressed:Arc<AtomicBool> = Arc::new(AtomicBool::new(false));
+				let (press_state, release_state) = (target_pressed.clone(), target_pressed);
+				hotkey
+					.on_press(move || {
+						press_inputs.execute();
+						press_state.store(true, Ordering::SeqCst);
+					})
+					.on_repeat(move || repeat_inputs.execute())
+					.on_release(move || {
+						if release_state.swap(false, Ordering::SeqCst) {
+							release_inputs.execute();
+						}
+					})
+					.register()
+			},
+			RemapTarget::Input(target) => {
+				hotkey
+					.on_press(move || drop(target.execute_async()))
+					.on_release(|| {})
+					.register()
+			}
+		}
+	}
+}
\ No newline at end of file
@@ -0,0 +1,73 @@
+#[cfg(test)]
+mod tests {
+	use crate::{ HotkeyHandle, InputAction, InputBuilder, Key, RecordingBackend, Remap, hokey_u::tests::HOTKEY_LOCK, input_builder_u::tests::{ GLOBAL_BACKEND_LOCK, global_recorder }, key_hook::simulate, keys };
+	use std::{ sync::MutexGuard, thread, time::{ Duration, Instant } };
+
+
+
+	/// Get all recorded actions of the given keys.
+	fn recorded_key_actions(recorder:&RecordingBackend, keys:&[Key]) -> Vec<InputAction> {
+		recorder.actions().into_iter().map(|recorded| recorded.action).filter(|action| action.key_alteration().map(|(key_code, _)| keys.iter().any(|key| key.key_code() == key_code)).unwrap_or(false)).collect()
+	}
+
+
+
+	#[test]
+	fn test_remap_key() {
+		let _hotkey_lock:MutexGuard<'_, ()> = HOTKEY_LOCK.lock().unwrap();
+		let _backend_lock:MutexGuard<'_, ()> = GLOBAL_BACKEND_LOCK.lock().unwrap();
+		let recorder:RecordingBackend = global_recorder();
+		let handle:HotkeyHandle = Remap::key(keys::F5, keys::F6).register();
+
+		assert!(simulate(keys::F5, true));
+		assert!(simulate(keys::F5, true));
+		assert!(simulate(keys::F5, false));
+		assert_eq!(recorded_key_actions(&recorder, &[keys::F5, keys::F6]), vec![
+			InputAction::KeyDown(keys::F6.key_code()),
+			InputAction::KeyDown(keys::F6.key_code()),
+			InputAction::KeyUp(keys::F6.key_code())
+		]);
+		handle.unregister();
+	}
+
+	#[test]
+	fn test_remap_pattern() {
+		let _hotkey_lock:MutexGuard<'_, ()> = HOTKEY_LOCK.lock().unwrap();
+		let _backend_lock:MutexGuard<'_, ()> = GLOBAL_BACKEND_LOCK.lock().unwrap();
+		let recorder:RecordingBackend = global_recorder();
+		let handle:HotkeyHandle = Remap::pattern(keys::F7, keys::RSHIFT.pattern() | keys::F8).register();
+
+		simulate(keys::F7, true);
+		simulate(keys::F7, false);
+		assert_eq!(recorded_key_actions(&recorder, &[keys::RSHIFT, keys::F7, keys::F8]), vec![
+			InputAction::KeyDown(keys::RSHIFT.key_code()),
+			InputAction::KeyDown(keys::F8.key_code()),
+			InputAction::KeyUp(keys::F8.key_code()),
+			InputAction::KeyUp(keys::RSHIFT.key_code())
+		]);
+		handle.unregister();
+	}
+
+	#[test]
+	fn test_remap_input() {
+		let _hotkey_lock:MutexGuard<'_, ()> = HOTKEY_LOCK.lock().unwrap();
+		let _backend_lock:MutexGuard<'_, ()> = GLOBAL_BACKEND_LOCK.lock().unwrap();
+		let recorder:RecordingBackend = global_recorder();
+		let handle:HotkeyHandle = Remap::input(keys::F9, InputBuilder::new().with_click(&keys::NUMPAD5).with_click(&keys::NUMPAD6)).register();
+
+		assert!(simulate(keys::F9, true));
+		assert!(simulate(keys::F9, true));
+		assert!(simulate(keys::F9, false));
+		let start:Instant = Instant::now();
+		while recorded_key_actions(&recorder, &[keys::NUMPAD5, keys::NUMPAD6]).len() < 4 && start.elapsed() < Duration::from_secs(2) {
+			thread::sleep(Duration::from_millis(5));
+		}
+		assert_eq!(recorded_key_actions(&recorder, &[keys::F9, keys::NUMPAD5, keys::NUMPAD6]), vec![
+			InputAction::KeyDown(keys::NUMPAD5.key_code()),
+			InputAction::KeyUp(keys::NUMPAD5.key_code()),
+			InputAction::KeyDown(keys::NUMPAD6.key_code()),
+			InputAction::KeyUp(keys::NUMPAD6.key_code())
+		]);
+		handle.unregister();
+	}
+}
\ No newline at end of file
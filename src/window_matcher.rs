@@ -0,0 +1,97 @@
+use crate::ActiveWindow;
+#[cfg(feature="regex")]
+use regex::Regex;
+
+
+
+enum WindowField { Class, Title }
+
+enum WindowPattern {
+	Glob(Vec<char>),
+	#[cfg(feature="regex")]
+	Regex(Regex)
+}
+
+
+
+/// Matches the class or title of a window. Used to scope hotkeys to specific applications.
+pub struct WindowMatcher {
+	field:WindowField,
+	pattern:WindowPattern
+}
+impl WindowMatcher {
+
+	/* CONSTRUCTOR METHODS */
+
+	/// Create a matcher for the class or instance name of a window, like "firefox". In the glob pattern, '*' matches any amount of characters and '?' matches a single character.
+	pub fn class(pattern:&str) -> WindowMatcher {
+		WindowMatcher { field: WindowField::Class, pattern: WindowPattern::Glob(pattern.chars().collect()) }
+	}
+
+	/// Create a matcher for the title of a window. In the glob pattern, '*' matches any amount of characters and '?' matches a single character.
+	pub fn title(pattern:&str) -> WindowMatcher {
+		WindowMatcher { field: WindowField::Title, pattern: WindowPattern::Glob(pattern.chars().collect()) }
+	}
+
+	/// Create a matcher for the class or instance name of a window using a regular expression. The expression matches anywhere in the name unless anchored (`regex` feature).
+	#[cfg(feature="regex")]
+	pub fn class_regex(pattern:&str) -> Result<WindowMatcher, regex::Error> {
+		Ok(WindowMatcher { field: WindowField::Class, pattern: WindowPattern::Regex(Regex::new(pattern)?) })
+	}
+
+	/// Create a matcher for the title of a window using a regular expression. The expression matches anywhere in the title unless anchored (`regex` feature).
+	#[cfg(feature="regex")]
+	pub fn title_regex(pattern:&str) -> Result<WindowMatcher, regex::Error> {
+		Ok(WindowMatcher { field: WindowField::Title, pattern: WindowPattern::Regex(Regex::new(pattern)?) })
+	}
+
+
+
+	/* USAGE METHODS */
+
+	/// Check if the matcher matches a window.
+	pub fn matches(&self, window:&ActiveWindow) -> bool {
+		match self.field {
+			WindowField::Class => self.matches_text(&window.class) || self.matches_text(&window.instance),
+			WindowField::Title => self.matches_text(&window.title)
+		}
+	}
+
+	/// Check if the pattern matches a text.
+	fn matches_text(&self, text:&str) -> bool {
+		match &self.pattern {
+			WindowPattern::Glob(pattern) => glob_matches(pattern, &text.chars().collect::<Vec<char>>()),
+			#[cfg(feature="regex")]
+			WindowPattern::Regex(regex) => regex.is_match(text)
+		}
+	}
+}
+
+
+
+/// Check if a glob pattern matches the entire text.
+fn glob_matches(pattern:&[char], text:&[char]) -> bool {
+	let (mut pattern_index, mut text_index):(usize, usize) = (0, 0);
+	let mut last_wildcard:Option<(usize, usize)> = None; // The position of the last '*' and the text index it currently matches up to.
+	while text_index < text.len() {
+		match pattern.get(pattern_index) {
+			Some('*') => {
+				last_wildcard = Some((pattern_index, text_index));
+				pattern_index += 1;
+			},
+			Some(character) if *character == '?' || *character == text[text_index] => {
+				pattern_index += 1;
+				text_index += 1;
+			},
+			_ => match last_wildcard {
+				Some((wildcard_index, wildcard_text_index)) => {
+					last_wildcard = Some((wildcard_index, wildcard_text_index + 1));
+					pattern_index = wildcard_index + 1;
+					text_index = wildcard_text_index + 1;
+				},
+				None => return false
+			}
+		}
+	}
+	pattern[pattern_index..].iter().all(|character| *character == '*')
+}
\ No newline at end of file
@@ -0,0 +1,36 @@
+#[cfg(test)]
+mod tests {
+	use crate::{ ActiveWindow, WindowMatcher };
+
+
+
+	#[test]
+	fn test_glob_class() {
+		let window:ActiveWindow = ActiveWindow::new("Alacritty", "alacritty", "~/projects");
+		assert!(WindowMatcher::class("alacritty").matches(&window));
+		assert!(WindowMatcher::class("Ala*").matches(&window));
+		assert!(WindowMatcher::class("*crit?y").matches(&window));
+		assert!(!WindowMatcher::class("alacritty?").matches(&window));
+		assert!(!WindowMatcher::class("firefox").matches(&window));
+	}
+
+	#[test]
+	fn test_glob_title() {
+		let window:ActiveWindow = ActiveWindow::new("firefox", "Navigator", "Issues - Mozilla Firefox");
+		assert!(WindowMatcher::title("*Mozilla Firefox").matches(&window));
+		assert!(WindowMatcher::title("*-*").matches(&window));
+		assert!(WindowMatcher::title("*").matches(&window));
+		assert!(!WindowMatcher::title("Issues").matches(&window));
+		assert!(!WindowMatcher::title("firefox").matches(&window));
+	}
+
+	#[cfg(feature="regex")]
+	#[test]
+	fn test_regex() {
+		let window:ActiveWindow = ActiveWindow::new("kitty", "kitty", "vim main.rs");
+		assert!(WindowMatcher::class_regex("^(kitty|alacritty)$").unwrap().matches(&window));
+		assert!(WindowMatcher::title_regex(r"\.rs$").unwrap().matches(&window));
+		assert!(!WindowMatcher::title_regex("^main").unwrap().matches(&window));
+		assert!(WindowMatcher::title_regex("(").is_err());
+	}
+}
\ No newline at end of file
@@ -0,0 +1,71 @@
+use std::sync::{ Arc, RwLock, RwLockWriteGuard };
+
+
+
+static WINDOW_SOURCE:RwLock<Option<Arc<dyn WindowSource>>> = RwLock::new(None);
+
+
+
+#[derive(Clone, Debug, PartialEq, Eq, Default)]
+pub struct ActiveWindow {
+	pub class:String,
+	pub instance:String,
+	pub title:String
+}
+impl ActiveWindow {
+
+	/// Create a new active window description.
+	pub fn new(class:&str, instance:&str, title:&str) -> ActiveWindow {
+		ActiveWindow {
+			class: class.to_string(),
+			instance: instance.to_string(),
+			title: title.to_string()
+		}
+	}
+}
+
+
+
+pub trait WindowSource:Send + Sync {
+
+	/// Get the window that currently has focus, or None if it cannot be determined.
+	fn active_window(&self) -> Option<ActiveWindow>;
+}
+
+
+
+/// A source that never knows the active window. Used when no native source is available.
+struct NullWindowSource;
+impl WindowSource for NullWindowSource {
+	fn active_window(&self) -> Option<ActiveWindow> { None }
+}
+
+
+
+/// Set the source the active window is read from.
+pub fn set_window_source<T>(source:T) where T:WindowSource + 'static {
+	*WINDOW_SOURCE.write().unwrap() = Some(Arc::new(source));
+}
+
+/// Get the source the active window is read from. Defaults to the native source of the platform.
+pub fn window_source() -> Arc<dyn WindowSource> {
+	if let Some(source) = WINDOW_SOURCE.read().unwrap().as_ref() {
+		return source.clone();
+	}
+	let mut source:RwLockWriteGuard<'_, Option<Arc<dyn WindowSource>>> = WINDOW_SOURCE.write().unwrap();
+	source.get_or_insert_with(default_window_source).clone()
+}
+
+/// Get the window that currently has focus from the window source.
+pub fn active_window() -> Option<ActiveWindow> {
+	window_source().active_window()
+}
+
+/// Create the native source of the platform.
+fn default_window_source() -> Arc<dyn WindowSource> {
+	#[cfg(all(target_os="linux", feature="x11"))]
+	if let Ok(source) = crate::X11WindowSource::new() {
+		return Arc::new(source);
+	}
+	Arc::new(NullWindowSource)
+}
\ No newline at end of file
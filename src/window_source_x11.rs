@@ -0,0 +1,175 @@
+use x11_dl::xlib::{ self, Display, XClassHint, XErrorEvent, Xlib };
+use std::{ error::Error, ffi::{ CStr, CString }, os::raw::{ c_char, c_int, c_uchar, c_ulong }, ptr, slice, sync::{ Mutex, MutexGuard, PoisonError, atomic::{ AtomicPtr, Ordering } } };
+use crate::{ ActiveWindow, WindowSource };
+
+
+
+type ErrorHandler = Option<unsafe extern "C" fn(*mut Display, *mut XErrorEvent) -> c_int>;
+static ERROR_HANDLER_LOCK:Mutex<()> = Mutex::new(());
+static IGNORED_DISPLAY:AtomicPtr<Display> = AtomicPtr::new(ptr::null_mut()); // Errors of other connections are passed on to the previous handler.
+static PREVIOUS_HANDLER:Mutex<ErrorHandler> = Mutex::new(None);
+
+
+
+// The display connection is only ever used while the mutex is locked, so it can be shared between threads.
+struct DisplayConnection(*mut Display);
+unsafe impl Send for DisplayConnection {}
+
+
+
+/// Replaces the Xlib error handler of the process with one that ignores errors of a single connection while alive. Errors of other connections are passed on to the previous handler. When dropped, pending errors are handled and the previous handler is restored.
+struct IgnoredErrors<'a> {
+	xlib:&'a Xlib,
+	display:*mut Display,
+	previous_handler:ErrorHandler,
+	_lock:MutexGuard<'static, ()> // Prevents sources on other threads from restoring the handlers out of order.
+}
+impl<'a> IgnoredErrors<'a> {
+
+	/// Ignore errors until the returned guard is dropped.
+	fn new(xlib:&'a Xlib, display:*mut Display) -> IgnoredErrors<'a> {
+		let lock:MutexGuard<'static, ()> = ERROR_HANDLER_LOCK.lock().unwrap_or_else(PoisonError::into_inner);
+		IGNORED_DISPLAY.store(display, Ordering::SeqCst);
+		let previous_handler:ErrorHandler = unsafe { (xlib.XSetErrorHandler)(Some(ignore_x_error)) };
+		*PREVIOUS_HANDLER.lock().unwrap_or_else(PoisonError::into_inner) = previous_handler;
+		IgnoredErrors { xlib, display, previous_handler, _lock: lock }
+	}
+}
+impl Drop for IgnoredErrors<'_> {
+	fn drop(&mut self) {
+		unsafe {
+			(self.xlib.XSync)(self.display, xlib::False);
+			(self.xlib.XSetErrorHandler)(self.previous_handler);
+		}
+		IGNORED_DISPLAY.store(ptr::null_mut(), Ordering::SeqCst);
+	}
+}
+
+
+
+/// The window source reading the active window from an X11 server. Requires a window manager that sets '_NET_ACTIVE_WINDOW' on the root window, which most do.
+/// The active window can be closed while it is being read, so while reading, the source replaces the Xlib error handler of the process with one that ignores the errors of its own connection instead of exiting. Errors of other connections, like those of other threads, are passed on to the previous handler, which is restored afterwards.
+pub struct X11WindowSource {
+	xlib:Xlib,
+	display:Mutex<DisplayConnection>,
+	active_window_atom:c_ulong,
+	window_name_atom:c_ulong,
+	utf8_string_atom:c_ulong
+}
+impl X11WindowSource {
+
+	/* CONSTRUCTOR METHODS */
+
+	/// Connect to the X11 server in the 'DISPLAY' environment variable.
+	pub fn new() -> Result<X11WindowSource, Box<dyn Error>> {
+		X11WindowSource::connect(None)
+	}
+
+	/// Connect to a specific X11 server, like ":99".
+	pub fn with_display(display_name:&str) -> Result<X11WindowSource, Box<dyn Error>> {
+		X11WindowSource::connect(Some(display_name))
+	}
+
+	/// Connect to the given or default X11 server.
+	fn connect(display_name:Option<&str>) -> Result<X11WindowSource, Box<dyn Error>> {
+		let xlib:Xlib = Xlib::open()?;
+		let display_name:Option<CString> = display_name.map(CString::new).transpose()?;
+		let display:*mut Display = unsafe { (xlib.XOpenDisplay)(display_name.as_ref().map(|name| name.as_ptr()).unwrap_or(ptr::null())) };
+		if display.is_null() {
+			return Err("Could not connect to X11 display.".into());
+		}
+		let intern_atom = |name:&CStr| unsafe { (xlib.XInternAtom)(display, name.as_ptr(), xlib::False) };
+		Ok(X11WindowSource {
+			active_window_atom: intern_atom(c"_NET_ACTIVE_WINDOW"),
+			window_name_atom: intern_atom(c"_NET_WM_NAME"),
+			utf8_string_atom: intern_atom(c"UTF8_STRING"),
+			xlib,
+			display: Mutex::new(DisplayConnection(display))
+		})
+	}
+
+
+
+	/* USAGE METHODS */
+
+	/// Read a property of a window. The reader receives the data and the amount of items in it.
+	unsafe fn read_property<T, U>(&self, display:*mut Display, window:c_ulong, property:c_ulong, property_type:c_ulong, reader:T) -> Option<U> where T:FnOnce(*const c_uchar, usize) -> U {
+		unsafe {
+			let mut actual_type:c_ulong = 0;
+			let mut actual_format:c_int = 0;
+			let mut item_count:c_ulong = 0;
+			let mut bytes_after:c_ulong = 0;
+			let mut data:*mut c_uchar = ptr::null_mut();
+			let status:c_int = (self.xlib.XGetWindowProperty)(display, window, property, 0, 1024, xlib::False, property_type, &mut actual_type, &mut actual_format, &mut item_count, &mut bytes_after, &mut data);
+			if data.is_null() {
+				return None;
+			}
+			let result:Option<U> = if status == xlib::Success as c_int && actual_type == property_type && item_count > 0 { Some(reader(data, item_count as usize)) } else { None };
+			(self.xlib.XFree)(data as *mut _);
+			result
+		}
+	}
+
+	/// Convert a string allocated by Xlib and free it.
+	unsafe fn take_x_string(&self, string:*mut c_char) -> String {
+		if string.is_null() {
+			return String::new();
+		}
+		unsafe {
+			let result:String = CStr::from_ptr(string).to_string_lossy().into_owned();
+			(self.xlib.XFree)(string as *mut _);
+			result
+		}
+	}
+}
+impl WindowSource for X11WindowSource {
+	fn active_window(&self) -> Option<ActiveWindow> {
+		let display:MutexGuard<'_, DisplayConnection> = self.display.lock().unwrap();
+		let _ignored_errors:IgnoredErrors<'_> = IgnoredErrors::new(&self.xlib, display.0);
+		unsafe {
+			let root:c_ulong = (self.xlib.XDefaultRootWindow)(display.0);
+			let window:c_ulong = self.read_property(display.0, root, self.active_window_atom, xlib::XA_WINDOW, |data, _| *(data as *const c_ulong))?;
+			if window == 0 {
+				return None;
+			}
+
+			// The class and instance names are both stored in 'WM_CLASS'.
+			let mut class_hint:XClassHint = XClassHint { res_name: ptr::null_mut(), res_class: ptr::null_mut() };
+			let (class, instance):(String, String) = if (self.xlib.XGetClassHint)(display.0, window, &mut class_hint) != 0 {
+				(self.take_x_string(class_hint.res_class), self.take_x_string(class_hint.res_name))
+			} else {
+				(String::new(), String::new())
+			};
+
+			// Prefer the UTF-8 title, fall back to 'WM_NAME'.
+			let title:String = match self.read_property(display.0, window, self.window_name_atom, self.utf8_string_atom, |data, length| String::from_utf8_lossy(slice::from_raw_parts(data, length)).into_owned()) {
+				Some(title) => title,
+				None => {
+					let mut name:*mut c_char = ptr::null_mut();
+					if (self.xlib.XFetchName)(display.0, window, &mut name) != 0 { self.take_x_string(name) } else { String::new() }
+				}
+			};
+			Some(ActiveWindow { class, instance, title })
+		}
+	}
+}
+impl Drop for X11WindowSource {
+	fn drop(&mut self) {
+		let display:MutexGuard<'_, DisplayConnection> = self.display.lock().unwrap();
+		unsafe { (self.xlib.XCloseDisplay)(display.0); }
+	}
+}
+
+
+
+/// Ignore errors caused by requests about windows that no longer exist, instead of exiting the process. Errors of other connections are passed on to the previous handler.
+unsafe extern "C" fn ignore_x_error(display:*mut Display, event:*mut XErrorEvent) -> c_int {
+	if display == IGNORED_DISPLAY.load(Ordering::SeqCst) {
+		return 0;
+	}
+	let previous_handler:ErrorHandler = *PREVIOUS_HANDLER.lock().unwrap_or_else(PoisonError::into_inner);
+	match previous_handler {
+		Some(handler) => unsafe { handler(display, event) },
+		None => 0
+	}
+}
\ No newline at end of file
@@ -0,0 +1,32 @@
+#[cfg(all(test, target_os="linux", feature="x11"))]
+mod tests {
+	use crate::{ ActiveWindow, WindowSource, X11WindowSource };
+	use x11_dl::xlib::{ self, Display, XClassHint, Xlib };
+	use std::{ os::raw::{ c_uchar, c_ulong }, ptr };
+
+
+
+	#[test]
+	#[ignore = "requires an X11 server, run with 'xvfb-run cargo test --features x11 -- --ignored'"]
+	fn test_active_window() {
+		let xlib:Xlib = Xlib::open().unwrap();
+		unsafe {
+			let display:*mut Display = (xlib.XOpenDisplay)(ptr::null());
+			assert!(!display.is_null());
+
+			// Create a dummy window and mark it active like a window manager would.
+			let root:c_ulong = (xlib.XDefaultRootWindow)(display);
+			let window:c_ulong = (xlib.XCreateSimpleWindow)(display, root, 0, 0, 10, 10, 0, 0, 0);
+			let mut class_hint:XClassHint = XClassHint { res_name: c"dummy_instance".as_ptr() as *mut _, res_class: c"DummyClass".as_ptr() as *mut _ };
+			(xlib.XSetClassHint)(display, window, &mut class_hint);
+			(xlib.XStoreName)(display, window, c"Dummy title".as_ptr());
+			let active_window_atom:c_ulong = (xlib.XInternAtom)(display, c"_NET_ACTIVE_WINDOW".as_ptr(), xlib::False);
+			(xlib.XChangeProperty)(display, root, active_window_atom, xlib::XA_WINDOW, 32, xlib::PropModeReplace, &window as *const c_ulong as *const c_uchar, 1);
+			(xlib.XSync)(display, xlib::False);
+
+			assert_eq!(X11WindowSource::new().unwrap().active_window(), Some(ActiveWindow::new("DummyClass", "dummy_instance", "Dummy title")));
+			(xlib.XDestroyWindow)(display, window);
+			(xlib.XCloseDisplay)(display);
+		}
+	}
+}
\ No newline at end of file